@@ -0,0 +1,63 @@
+//! Finds the classes and enums that are declared identically in every version.
+
+use std::collections::{HashMap, HashSet};
+use crate::ir::{ClassDecl, EnumDecl, TypeModel, TypeRef};
+use crate::reachability::collect_names;
+
+fn references_any(type_ref: &TypeRef, names: &HashSet<&str>) -> bool {
+    let mut referenced = Vec::new();
+    collect_names(type_ref, &mut referenced);
+    referenced.iter().any(|name| names.contains(name.as_str()))
+}
+
+/// Keeps the classes and enums of the first model that every other model declares the same way, including sizes
+/// and offsets. Classes referencing a type that isn't shared aren't shared either.
+///
+/// Types named in `excluded` are never shared.
+pub fn shared_types(models: &[TypeModel], excluded: &[&str]) -> TypeModel {
+    let Some((first, others)) = models.split_first() else {
+        return TypeModel::default();
+    };
+
+    // Index the other models by name, `TypeModel::class` is a linear search.
+    let other_classes = others.iter()
+        .map(|model| model.classes.iter().map(|class| (class.name.as_str(), class)).collect::<HashMap<_, _>>())
+        .collect::<Vec<_>>();
+    let other_enums = others.iter()
+        .map(|model| model.enums.iter().map(|enum_decl| (enum_decl.name.as_str(), enum_decl)).collect::<HashMap<_, _>>())
+        .collect::<Vec<_>>();
+
+    let enums = first.enums.iter()
+        .filter(|enum_decl| !excluded.contains(&enum_decl.name.as_str()))
+        .filter(|enum_decl| other_enums.iter().all(|model| model.get(enum_decl.name.as_str()) == Some(enum_decl)))
+        .collect::<Vec<&EnumDecl>>();
+    let mut classes = first.classes.iter()
+        .filter(|class| !excluded.contains(&class.name.as_str()))
+        .filter(|class| other_classes.iter().all(|model| model.get(class.name.as_str()) == Some(class)))
+        .collect::<Vec<&ClassDecl>>();
+
+    // Every declared type that differs between the versions, or only exists in some of them.
+    let shared = classes.iter().map(|class| class.name.as_str())
+        .chain(enums.iter().map(|enum_decl| enum_decl.name.as_str()))
+        .collect::<HashSet<_>>();
+    let mut unshared = models.iter()
+        .flat_map(|model| model.classes.iter().map(|class| class.name.as_str()).chain(model.enums.iter().map(|enum_decl| enum_decl.name.as_str())))
+        .filter(|name| !shared.contains(name))
+        .collect::<HashSet<_>>();
+
+    // Unsharing a class can unshare the classes using it, so repeat until nothing changes.
+    loop {
+        let (kept, dropped): (Vec<_>, Vec<_>) = classes.into_iter()
+            .partition(|class| !class.fields.iter().any(|field| references_any(&field.type_ref, &unshared)));
+        classes = kept;
+        if dropped.is_empty() {
+            break;
+        }
+        unshared.extend(dropped.iter().map(|class| class.name.as_str()));
+    }
+
+    TypeModel {
+        classes: classes.into_iter().cloned().collect(),
+        enums: enums.into_iter().cloned().collect(),
+    }
+}
@@ -0,0 +1,801 @@
+//! Writes the Rust bindings of a type model.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::fs::File;
+use std::io::{self, Write, BufWriter};
+use std::path::Path;
+use crate::ir::{ClassDecl, EnumDecl, FieldDecl, TypeModel, TypeRef};
+
+/// Where a bindings module is generated to and how it reaches the shared types.
+pub struct Module<'a> {
+    /// The name of the directory in `OUT_DIR` the module is written to.
+    pub name: &'a str,
+    /// The path the module is included at, e.g. `crate` or `crate::common`.
+    pub path: &'a str,
+    /// The path of the glacier-types crate from inside the module.
+    pub types_crate: &'a str,
+    /// Types already generated by glacier-types. They are re-exported instead of generated again.
+    pub shared: Option<&'a TypeModel>,
+    /// The resource types of the module with their root classes, e.g. `("TEMP", "STemplateEntityFactory")`.
+    pub resource_roots: &'a [(String, &'a str)],
+}
+
+/// Generates the bindings module for a type model into `OUT_DIR`, along with the model as JSON.
+pub fn generate_bindings(model: &TypeModel, module: &Module) -> io::Result<()> {
+    // The bindings are written to OUT_DIR and pulled into the crate with include!.
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is not set");
+    let output_dir = Path::new(&out_dir).join(module.name);
+    fs::create_dir_all(&output_dir)?;
+
+    let mut enums_buffer = BufWriter::new(File::create(output_dir.join("enums.rs"))?);
+    let mut properties_buffer = BufWriter::new(File::create(output_dir.join("properties.rs"))?);
+    let mut visitor_buffer = BufWriter::new(File::create(output_dir.join("visitor.rs"))?);
+    let mut mod_buffer = BufWriter::new(File::create(output_dir.join("mod.rs"))?);
+
+    // Nested modules have to be included from OUT_DIR as well; `mod x;` would resolve relative to the including file.
+    // Not every module needs all of the common imports.
+    for submodule in ["enums", "properties", "visitor"] {
+        writeln!(mod_buffer, "#[allow(unused_imports)]")?;
+        writeln!(mod_buffer, "pub mod {} {{", submodule)?;
+        writeln!(mod_buffer, "\tinclude!(concat!(env!(\"OUT_DIR\"), \"/{}/{}.rs\"));", module.name, submodule)?;
+        writeln!(mod_buffer, "}}")?;
+    }
+    generate_type_model_accessor(model, module, &out_dir, &mut mod_buffer)?;
+    generate_schema_accessor(model, module, &out_dir, &mut mod_buffer)?;
+
+    // The visitor covers the shared types as well.
+    generate_visitor(model, module, &mut visitor_buffer)?;
+
+    // Computed before dropping the shared types, the classes of this module can contain them.
+    let without_default = types_without_default(model);
+    let plain = plain_classes(model, module.shared);
+    let declared = model.classes.iter().map(|class| class.name.as_str())
+        .chain(model.enums.iter().map(|enum_decl| enum_decl.name.as_str()))
+        .collect::<HashSet<_>>();
+
+    let shared = module.shared.map(|shared| {
+        shared.classes.iter().map(|class| class.name.as_str())
+            .chain(shared.enums.iter().map(|enum_decl| enum_decl.name.as_str()))
+            .collect::<HashSet<_>>()
+    }).unwrap_or_default();
+    let model = TypeModel {
+        classes: model.classes.iter().filter(|class| !shared.contains(class.name.as_str())).cloned().collect(),
+        enums: model.enums.iter().filter(|enum_decl| !shared.contains(enum_decl.name.as_str())).cloned().collect(),
+    };
+
+    // Generate code for enums.
+    generate_enums(&model, module, &mut enums_buffer)?;
+
+    // Generate code for classes (properties).
+    generate_properties(&model, module, &without_default, &plain, &declared, &mut properties_buffer)?;
+
+    enums_buffer.flush()?;
+    properties_buffer.flush()?;
+    visitor_buffer.flush()?;
+    mod_buffer.flush()
+}
+
+/// Writes the type model to `OUT_DIR` and exposes it from the bindings module.
+fn generate_type_model_accessor<W: io::Write>(model: &TypeModel, module: &Module, out_dir: &str, buffer: &mut W) -> io::Result<()> {
+    let model_path = Path::new(out_dir).join(format!("{}.json", module.name));
+    serde_json::to_writer(BufWriter::new(File::create(model_path)?), model)?;
+
+    writeln!(buffer)?;
+    writeln!(buffer, "/// The type model these bindings were generated from, as JSON.")?;
+    writeln!(buffer, "pub const TYPE_MODEL_JSON: &str = include_str!(concat!(env!(\"OUT_DIR\"), \"/{}.json\"));", module.name)?;
+    writeln!(buffer)?;
+    writeln!(buffer, "/// The classes and enums these bindings were generated from, including their sizes, alignments and offsets.")?;
+    writeln!(buffer, "pub fn type_model() -> &'static {}::ir::TypeModel {{", module.types_crate)?;
+    writeln!(buffer, "\tstatic TYPE_MODEL: std::sync::OnceLock<{}::ir::TypeModel> = std::sync::OnceLock::new();", module.types_crate)?;
+    writeln!(buffer, "\tTYPE_MODEL.get_or_init(|| serde_json::from_str(TYPE_MODEL_JSON).expect(\"invalid type model\"))")?;
+    writeln!(buffer, "}}")?;
+    Ok(())
+}
+
+fn generate_schema_accessor<W: io::Write>(model: &TypeModel, module: &Module, out_dir: &str, buffer: &mut W) -> io::Result<()> {
+    let schema_path = Path::new(out_dir).join(format!("{}.schema.json", module.name));
+    serde_json::to_writer(BufWriter::new(File::create(schema_path)?), &crate::schema::json_schema(model))?;
+
+    writeln!(buffer)?;
+    writeln!(buffer, "/// The JSON Schema of the JSON ResourceLib reads and writes for these bindings, with a definition for every class")?;
+    writeln!(buffer, "/// and enum in `$defs`.")?;
+    writeln!(buffer, "pub const JSON_SCHEMA: &str = include_str!(concat!(env!(\"OUT_DIR\"), \"/{}.schema.json\"));", module.name)?;
+    writeln!(buffer)?;
+    writeln!(buffer, "/// The resource types of these bindings with the C++ name of their root class.")?;
+    let roots = module.resource_roots.iter()
+        .map(|(resource_type, root)| format!("(\"{}\", \"{}\")", resource_type, root))
+        .collect::<Vec<_>>();
+    writeln!(buffer, "pub const RESOURCE_ROOTS: &[(&str, &str)] = &[{}];", roots.join(", "))?;
+    Ok(())
+}
+
+fn write_includes<W: io::Write>(module: &Module, buffer: &mut W) -> io::Result<()>{
+    writeln!(buffer, "use {}::*;", module.types_crate)?;
+    writeln!(buffer, "use serde::{{ Serialize, Deserialize }};")?;
+    writeln!(buffer, "use std::collections::HashMap;")?;
+    writeln!(buffer, "use serde_big_array::BigArray;")?;
+    writeln!(buffer)?;
+    Ok(())
+}
+
+/// The `ZVariant`s glacier-types implements by hand, by their type name.
+pub(crate) const BUILTIN_VARIANTS: [(&str, &str); 18] = [
+    ("void", "Empty"),
+    ("bool", "bool"),
+    ("char", "char"),
+    ("int8", "i8"),
+    ("int16", "i16"),
+    ("int32", "i32"),
+    ("int64", "i64"),
+    ("uint8", "u8"),
+    ("uint16", "u16"),
+    ("uint32", "u32"),
+    ("uint64", "u64"),
+    ("float32", "f32"),
+    ("float64", "f64"),
+    ("String", "String"),
+    ("ZString", "ZString"),
+    ("ZEncryptedString", "ZEncryptedString"),
+    ("ZHMArenas", "ZHMArenas"),
+    ("ZRepositoryID", "ZRepositoryId"),
+];
+
+/// Generates a `Visitor` trait with a method for every `ZVariant` type and `TArray` of it, and a `visit` function
+/// calling the method for the type of a value.
+fn generate_visitor<W: io::Write>(model: &TypeModel, module: &Module, buffer: &mut W) -> io::Result<()> {
+    write_includes(module, buffer)?;
+    writeln!(buffer, "use {}::enums::*;", module.path)?;
+    writeln!(buffer, "use {}::properties::*;", module.path)?;
+    writeln!(buffer)?;
+
+    // (type name, Rust type, method), for every type and its TArray.
+    let mut visited = Vec::new();
+    let mut methods = HashSet::from(["visit_other".to_owned()]);
+    let mut type_names = HashSet::new();
+    let builtins = BUILTIN_VARIANTS.iter().map(|(type_name, rust_type)| (type_name.to_string(), rust_type.to_string()));
+    let enums = model.enums.iter().map(|enum_decl| (variant_handle(&enum_decl.name, true), heck::AsUpperCamelCase(&enum_decl.name).to_string()));
+    let classes = model.classes.iter().map(|class| (variant_handle(&class.name, false), heck::AsUpperCamelCase(&class.name).to_string()));
+    for (type_name, rust_type) in builtins.chain(enums).chain(classes) {
+        let method = format!("visit_{}", heck::AsSnakeCase(&rust_type));
+        // Enums with the same Rust name are only generated once.
+        if methods.contains(&method) || type_names.contains(&type_name) {
+            continue;
+        }
+        methods.insert(method.clone());
+        type_names.insert(type_name.clone());
+        // `void` can't be stored in an array.
+        if rust_type != "Empty" {
+            visited.push((format!("TArray<{}>", type_name), format!("TArray<{}>", rust_type), format!("{}_array", method)));
+        }
+        visited.push((type_name, rust_type, method));
+    }
+
+    writeln!(buffer, "/// Visits the value of a `ZVariant` by its type, see [`visit`]. Every method defaults to `visit_other`.")?;
+    writeln!(buffer, "pub trait Visitor {{")?;
+    writeln!(buffer, "	/// Called for the values of types without a method, and by the methods that aren't implemented.")?;
+    writeln!(buffer, "	fn visit_other(&mut self, _value: &dyn ZVariant) {{}}")?;
+    for (_, rust_type, method) in &visited {
+        writeln!(buffer, "	fn {}(&mut self, value: &{}) {{ self.visit_other(value) }}", method, rust_type)?;
+    }
+    writeln!(buffer, "}}")?;
+    writeln!(buffer)?;
+
+    writeln!(buffer, "/// Calls the method of `visitor` for the type of `value`.")?;
+    writeln!(buffer, "pub fn visit<V: Visitor + ?Sized>(visitor: &mut V, value: &dyn ZVariant) {{")?;
+    writeln!(buffer, "	match value.type_name() {{")?;
+    for (type_name, _, method) in &visited {
+        // The name can belong to the type of another version.
+        writeln!(buffer, "		\"{}\" => match value.get() {{ Some(value) => visitor.{}(value), None => visitor.visit_other(value) }},", type_name, method)?;
+    }
+    writeln!(buffer, "		_ => visitor.visit_other(value),")?;
+    writeln!(buffer, "	}}")?;
+    writeln!(buffer, "}}")?;
+    Ok(())
+}
+
+/// Generates Rust code for all C++ enums in the type model.
+fn generate_enums<W: io::Write>(model: &TypeModel, module: &Module, buffer: &mut W) -> io::Result<()> {
+    let mut written_symbols = HashSet::new();
+
+    write_includes(module, buffer)?;
+    if module.shared.is_some() {
+        writeln!(buffer, "pub use {}::common::enums::*;", module.types_crate)?;
+    }
+
+    for enum_decl in &model.enums {
+        let rust_enum_name = heck::AsUpperCamelCase(&enum_decl.name).to_string();
+
+        // Skip if we've already processed this symbol (avoid duplicates).
+        if !written_symbols.insert(rust_enum_name.clone()) {
+            continue;
+        }
+
+        generate_enum(enum_decl, &rust_enum_name, module, buffer)?;
+    }
+    Ok(())
+}
+
+fn generate_enum<W: io::Write>(enum_decl: &EnumDecl, rust_enum_name: &str, module: &Module, buffer: &mut W) -> io::Result<()> {
+    let original_enum_name = enum_decl.name.as_str();
+    let mut fields = enum_decl.variants.iter()
+        .map(|variant| (variant.name.clone(), heck::AsUpperCamelCase(&variant.name).to_string(), variant.value))
+        .collect::<Vec<_>>();
+
+    // Remove common prefix if it exists (e.g., "E_" from all variants).
+    remove_common_prefix_from_enum_variants(&mut fields);
+
+    // Rust enums can't repeat a discriminant, so the first variant with a value is the canonical one and the later
+    // ones become serde aliases and associated constants of it.
+    let mut canonical = Vec::new();
+    let mut aliases = Vec::new();
+    let mut canonical_by_value = HashMap::new();
+    for (original_field, rust_field, value) in fields {
+        match canonical_by_value.get(&value) {
+            Some(&index) => aliases.push((index, original_field, rust_field)),
+            None => {
+                canonical_by_value.insert(value, canonical.len());
+                canonical.push((original_field, rust_field, value));
+            }
+        }
+    }
+
+    // The engine zero-initializes enums, so default to the variant with value 0 if there is one.
+    let default_index = canonical.iter().position(|(_, _, value)| *value == 0).unwrap_or(0);
+
+    // Values ZHMGen.h doesn't declare go to a catch-all variant, named `Unknown` unless a declared variant is.
+    let unknown_variant = crate::feature_enabled("unknown-enum-values").then(|| {
+        let mut name = "Unknown".to_owned();
+        while canonical.iter().map(|(_, rust_field, _)| rust_field).chain(aliases.iter().map(|(_, _, rust_alias)| rust_alias)).any(|rust_name| *rust_name == name) {
+            name.push('_');
+        }
+        name
+    });
+    let copy = if unknown_variant.is_some() { "" } else { "Copy, " };
+    let default = if canonical.is_empty() { "" } else { "Default, " };
+
+    writeln!(buffer, "/// Underlying type: `{}`.", enum_decl.underlying_type)?;
+    writeln!(buffer, "#[derive(Debug, Clone, {}PartialEq, Eq, Hash, {}Serialize, Deserialize)]", copy, default)?;
+    writeln!(buffer, "#[serde(rename = \"{}\")]", original_enum_name)?;
+    if unknown_variant.is_some() {
+        // Explicit discriminants on an enum with a tuple variant need a primitive representation.
+        writeln!(buffer, "#[repr(i64)]")?;
+    }
+    writeln!(buffer, "pub enum {} {{", rust_enum_name)?;
+    for (index, (original_field, rust_field, value)) in canonical.iter().enumerate() {
+        if index == default_index {
+            writeln!(buffer, "\t#[default]")?;
+        }
+        writeln!(buffer, "\t#[serde(rename = \"{}\")]", original_field)?;
+        for (_, original_alias, _) in aliases.iter().filter(|(canonical_index, _, _)| *canonical_index == index) {
+            writeln!(buffer, "\t#[serde(alias = \"{}\")]", original_alias)?;
+        }
+        writeln!(buffer, "\t{} = {},", rust_field, value)?;
+    }
+    if let Some(unknown_variant) = &unknown_variant {
+        writeln!(buffer, "\t/// A value ZHMGen.h doesn't declare, serialized back unchanged.")?;
+        writeln!(buffer, "\t#[serde(untagged)]")?;
+        writeln!(buffer, "\t{}(UnknownEnumValue),", unknown_variant)?;
+    }
+    writeln!(buffer, "}}")?;
+    writeln!(buffer)?;
+
+    // A constant named like a variant or another constant can't be declared, those aliases only exist for serde.
+    let mut constant_names = canonical.iter().map(|(_, rust_field, _)| rust_field.as_str()).chain(unknown_variant.as_deref()).collect::<HashSet<_>>();
+    let constants = aliases.iter()
+        .filter(|(_, _, rust_alias)| constant_names.insert(rust_alias.as_str()))
+        .collect::<Vec<_>>();
+    if !constants.is_empty() {
+        writeln!(buffer, "impl {} {{", rust_enum_name)?;
+        for (index, original_alias, rust_alias) in constants {
+            writeln!(buffer, "\t/// `{}`, an alias of [`{}::{}`].", original_alias, rust_enum_name, canonical[*index].1)?;
+            writeln!(buffer, "\t#[allow(non_upper_case_globals)]")?;
+            writeln!(buffer, "\tpub const {}: {} = {}::{};", rust_alias, rust_enum_name, rust_enum_name, canonical[*index].1)?;
+        }
+        writeln!(buffer, "}}")?;
+        writeln!(buffer)?;
+    }
+
+    // Register ZVariant trait using the original name or safe modifications
+    writeln!(buffer, "{}::zvariant!({}, \"{}\");", module.types_crate, rust_enum_name, variant_handle(original_enum_name, true))?;
+    writeln!(buffer)?;
+
+    let variants = canonical.iter().map(|(original_field, _, _)| format!("\"{}\"", original_field)).collect::<Vec<_>>();
+    generate_reflect(rust_enum_name, original_enum_name, "Enum", None, &[], &variants.join(", "), module, buffer)
+}
+
+/// A field of a generated class, for the `Reflect` implementation.
+struct ReflectedField<'a> {
+    field: &'a FieldDecl,
+    rust_name: String,
+    big_array: bool,
+}
+
+/// The name of the constant holding the offset of a field, e.g. `OFFSET_PROPERTY_ID`.
+fn offset_constant(rust_field_name: &str) -> String {
+    format!("OFFSET_{}", rust_field_name.trim_end_matches('_').to_uppercase())
+}
+
+/// Generates the `SIZE`, `ALIGN` and `OFFSET_*` constants of a class, for the layout ZHMGen.h was generated from.
+fn generate_layout<W: io::Write>(rust_class_name: &str, class: &ClassDecl, fields: &[ReflectedField], buffer: &mut W) -> io::Result<()> {
+    let offsets = fields.iter()
+        .filter_map(|ReflectedField { field, rust_name, .. }| field.offset.map(|offset| (offset_constant(rust_name), offset)))
+        .collect::<Vec<_>>();
+    if class.size.is_none() && class.alignment.is_none() && offsets.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(buffer, "impl {} {{", rust_class_name)?;
+    if let Some(size) = class.size {
+        writeln!(buffer, "\tpub const SIZE: usize = {:#x};", size)?;
+    }
+    if let Some(alignment) = class.alignment {
+        writeln!(buffer, "\tpub const ALIGN: usize = {};", alignment)?;
+    }
+    for (constant, offset) in offsets {
+        writeln!(buffer, "\tpub const {}: usize = {:#x};", constant, offset)?;
+    }
+    writeln!(buffer, "}}")?;
+    writeln!(buffer)?;
+    Ok(())
+}
+
+/// Implements `GameStruct` for a plain class, reading and writing every field at its offset.
+fn generate_game_struct<W: io::Write>(class: &ClassDecl, rust_class_name: &str, fields: &[ReflectedField], module: &Module, buffer: &mut W) -> io::Result<()> {
+    let binary = format!("{}::binary", module.types_crate);
+    // Resource files list where their runtime resource IDs are.
+    let runtime_resource_id = class.name == "ZRuntimeResourceID";
+    // Classes without fields don't read or write anything.
+    let (reader, writer) = if fields.is_empty() { ("_reader", "_writer") } else { ("reader", "writer") };
+    let offset = if fields.is_empty() && !runtime_resource_id { "_offset" } else { "offset" };
+    let writer = if runtime_resource_id { "writer" } else { writer };
+
+    writeln!(buffer, "impl {}::GameStruct for {} {{", binary, rust_class_name)?;
+    writeln!(buffer, "\tconst SIZE: usize = {}::SIZE;", rust_class_name)?;
+    writeln!(buffer, "\tconst ALIGN: usize = {}::ALIGN;", rust_class_name)?;
+    writeln!(buffer)?;
+    writeln!(buffer, "\tfn read({}: &{}::BinaryReader, {}: usize) -> Result<Self, {}::BinaryError> {{", reader, binary, offset, binary)?;
+    writeln!(buffer, "\t\tOk({} {{", rust_class_name)?;
+    for ReflectedField { rust_name, .. } in fields {
+        writeln!(buffer, "\t\t\t{}: reader.read(offset + Self::{})?,", rust_name, offset_constant(rust_name))?;
+    }
+    writeln!(buffer, "\t\t}})")?;
+    writeln!(buffer, "\t}}")?;
+    writeln!(buffer)?;
+    writeln!(buffer, "\tfn write(&self, {}: &mut {}::BinaryWriter, {}: usize) {{", writer, binary, offset)?;
+    for ReflectedField { rust_name, .. } in fields {
+        writeln!(buffer, "\t\t{}::GameStruct::write(&self.{}, writer, offset + Self::{});", binary, rust_name, offset_constant(rust_name))?;
+    }
+    if runtime_resource_id {
+        writeln!(buffer, "\t\twriter.register_runtime_resource_id(offset);")?;
+    }
+    writeln!(buffer, "\t}}")?;
+    writeln!(buffer, "}}")?;
+    writeln!(buffer)?;
+    Ok(())
+}
+
+/// Generates the `TYPE_INFO` constant of a class or enum and its `Reflect` implementation. The layout of a class is
+/// taken from the constants of `generate_layout`.
+#[allow(clippy::too_many_arguments)]
+fn generate_reflect<W: io::Write>(rust_type: &str, original_name: &str, kind: &str, class: Option<&ClassDecl>, fields: &[ReflectedField], variants: &str, module: &Module, buffer: &mut W) -> io::Result<()> {
+    let reflect = format!("{}::reflect", module.types_crate);
+    let unknown_field = format!("{}::ReflectError::UnknownField {{ type_name: \"{}\", field: name.to_owned() }}", reflect, original_name);
+    // Enums have no fields to match on.
+    let name = if fields.is_empty() { "_name" } else { "name" };
+
+    let size = class.and_then(|class| class.size).map_or("None", |_| "Some(Self::SIZE)");
+    let alignment = class.and_then(|class| class.alignment).map_or("None", |_| "Some(Self::ALIGN)");
+    writeln!(buffer, "impl {} {{", rust_type)?;
+    writeln!(buffer, "\tpub const TYPE_INFO: {0}::TypeInfo = {0}::TypeInfo {{", reflect)?;
+    writeln!(buffer, "\t\tname: \"{}\",", original_name)?;
+    writeln!(buffer, "\t\tkind: {}::TypeKind::{},", reflect, kind)?;
+    writeln!(buffer, "\t\tsize: {},", size)?;
+    writeln!(buffer, "\t\talignment: {},", alignment)?;
+    writeln!(buffer, "\t\tfields: &[")?;
+    for ReflectedField { field, rust_name, .. } in fields {
+        let offset = field.offset.map_or("None".to_owned(), |_| format!("Some(Self::{})", offset_constant(rust_name)));
+        writeln!(buffer, "\t\t\t{}::FieldInfo {{ name: \"{}\", rust_name: \"{}\", type_name: \"{}\", offset: {} }},", reflect, field.name, rust_name, field.type_ref, offset)?;
+    }
+    writeln!(buffer, "\t\t],")?;
+    writeln!(buffer, "\t\tvariants: &[{}],", variants)?;
+    writeln!(buffer, "\t}};")?;
+    writeln!(buffer, "}}")?;
+    writeln!(buffer)?;
+
+    writeln!(buffer, "impl {}::Reflect for {} {{", reflect, rust_type)?;
+    writeln!(buffer, "\tfn type_info(&self) -> &'static {}::TypeInfo {{", reflect)?;
+    writeln!(buffer, "\t\t&Self::TYPE_INFO")?;
+    writeln!(buffer, "\t}}")?;
+    writeln!(buffer)?;
+
+    for (method, receiver, reference) in [("field", "&self", "&"), ("field_mut", "&mut self", "&mut ")] {
+        writeln!(buffer, "\tfn {}({}, {}: &str) -> Option<{}dyn std::any::Any> {{", method, receiver, name, reference)?;
+        if fields.is_empty() {
+            writeln!(buffer, "\t\tNone")?;
+        } else {
+            writeln!(buffer, "\t\tmatch {} {{", name)?;
+            for ReflectedField { field, rust_name, .. } in fields {
+                writeln!(buffer, "\t\t\t\"{}\" => Some({}self.{}),", field.name, reference, rust_name)?;
+            }
+            writeln!(buffer, "\t\t\t_ => None,")?;
+            writeln!(buffer, "\t\t}}")?;
+        }
+        writeln!(buffer, "\t}}")?;
+        writeln!(buffer)?;
+    }
+
+    if fields.is_empty() {
+        writeln!(buffer, "\tfn get_field(&self, name: &str) -> Result<serde_json::Value, {}::ReflectError> {{", reflect)?;
+        writeln!(buffer, "\t\tErr({})", unknown_field)?;
+        writeln!(buffer, "\t}}")?;
+        writeln!(buffer)?;
+        writeln!(buffer, "\tfn set_field(&mut self, name: &str, _value: serde_json::Value) -> Result<(), {}::ReflectError> {{", reflect)?;
+        writeln!(buffer, "\t\tErr({})", unknown_field)?;
+        writeln!(buffer, "\t}}")?;
+        writeln!(buffer, "}}")?;
+        writeln!(buffer)?;
+        return Ok(());
+    }
+
+    // The fields are borrowed whether they're `Copy` or not.
+    writeln!(buffer, "\t#[allow(clippy::needless_borrows_for_generic_args)]")?;
+    writeln!(buffer, "\tfn get_field(&self, name: &str) -> Result<serde_json::Value, {}::ReflectError> {{", reflect)?;
+    writeln!(buffer, "\t\tlet (field, value) = match name {{")?;
+    for ReflectedField { field, rust_name, big_array } in fields {
+        // Serde only implements the traits for arrays of up to 32 elements.
+        let value = if *big_array {
+            format!("BigArray::serialize(&self.{}, serde_json::value::Serializer)", rust_name)
+        } else {
+            format!("serde_json::to_value(&self.{})", rust_name)
+        };
+        writeln!(buffer, "\t\t\t\"{}\" => (\"{}\", {}),", field.name, field.name, value)?;
+    }
+    writeln!(buffer, "\t\t\t_ => return Err({}),", unknown_field)?;
+    writeln!(buffer, "\t\t}};")?;
+    writeln!(buffer, "\t\tvalue.map_err(|source| {}::ReflectError::InvalidValue {{ type_name: \"{}\", field, source }})", reflect, original_name)?;
+    writeln!(buffer, "\t}}")?;
+    writeln!(buffer)?;
+
+    writeln!(buffer, "\tfn set_field(&mut self, name: &str, value: serde_json::Value) -> Result<(), {}::ReflectError> {{", reflect)?;
+    writeln!(buffer, "\t\tlet (field, result) = match name {{")?;
+    for ReflectedField { field, rust_name, big_array } in fields {
+        let value = if *big_array { "BigArray::deserialize(value)" } else { "serde_json::from_value(value)" };
+        writeln!(buffer, "\t\t\t\"{}\" => (\"{}\", {}.map(|value| self.{} = value)),", field.name, field.name, value, rust_name)?;
+    }
+    writeln!(buffer, "\t\t\t_ => return Err({}),", unknown_field)?;
+    writeln!(buffer, "\t\t}};")?;
+    writeln!(buffer, "\t\tresult.map_err(|source| {}::ReflectError::InvalidValue {{ type_name: \"{}\", field, source }})", reflect, original_name)?;
+    writeln!(buffer, "\t}}")?;
+    writeln!(buffer, "}}")?;
+    writeln!(buffer)?;
+    Ok(())
+}
+
+fn generate_properties<W: io::Write>(model: &TypeModel, module: &Module, without_default: &HashSet<&str>, plain: &HashSet<&str>, declared: &HashSet<&str>, buffer: &mut W) -> io::Result<()>{
+    write_includes(module, buffer)?;
+    writeln!(buffer, "use {}::enums::*;", module.path)?;
+    if module.shared.is_some() {
+        writeln!(buffer, "pub use {}::common::properties::*;", module.types_crate)?;
+    }
+
+    let mut unmapped = Vec::new();
+    for class in &model.classes {
+        let class_traits = ClassTraits { default: !without_default.contains(class.name.as_str()), plain: plain.contains(class.name.as_str()) };
+        generate_class(class, module, class_traits, declared, &mut unmapped, buffer)?;
+    }
+
+    writeln!(buffer, "/// The `TypeInfo` of every class generated in this module, with its size, alignment and field offsets.")?;
+    if module.shared.is_some() {
+        writeln!(buffer, "/// The classes shared by every version are in `{}::common::properties::CLASSES`.", module.types_crate)?;
+    }
+    writeln!(buffer, "pub const CLASSES: &[&{}::reflect::TypeInfo] = &[", module.types_crate)?;
+    for class in &model.classes {
+        writeln!(buffer, "\t&{}::TYPE_INFO,", heck::AsUpperCamelCase(&class.name))?;
+    }
+    writeln!(buffer, "];")?;
+    writeln!(buffer)?;
+
+    // Skipping the fields would silently drop data from every resource using them.
+    if !unmapped.is_empty() {
+        return Err(io::Error::other(format!("Fields without a Rust mapping:\n{}", unmapped.join("\n"))));
+    }
+    Ok(())
+}
+
+/// The optional traits of a generated class.
+#[derive(Clone, Copy)]
+struct ClassTraits {
+    /// Whether the class can derive `Default`.
+    default: bool,
+    /// Whether the class is plain and implements `GameStruct`.
+    plain: bool,
+}
+
+fn generate_class<W: io::Write>(class: &ClassDecl, module: &Module, traits: ClassTraits, declared: &HashSet<&str>, unmapped: &mut Vec<String>, buffer: &mut W) -> io::Result<()> {
+    let original_class_name = class.name.as_str();
+    let rust_class_name = heck::AsUpperCamelCase(original_class_name).to_string();
+
+    if let (Some(size), Some(alignment)) = (class.size, class.alignment) {
+        writeln!(buffer, "/// Size: {:#x}, alignment: {}.", size, alignment)?;
+    }
+    if traits.default {
+        writeln!(buffer, "#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]")?;
+    } else {
+        writeln!(buffer, "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]")?;
+    }
+    writeln!(buffer, "#[serde(rename = \"{}\")]", original_class_name)?;
+    writeln!(buffer, "pub struct {} {{", rust_class_name)?;
+
+    let mut reflected = Vec::new();
+    for field in &class.fields {
+        // Special case
+        let rust_type = if rust_class_name == "SEntityTemplateProperty" && field.name == "nPropertyID" {
+            "EntityTemplatePropertyId".to_owned()
+        } else if let Some(rust_type) = map_cpp_type_to_rust(&field.type_ref, declared) {
+            rust_type
+        } else {
+            unmapped.push(format!("{}::{}: {}", original_class_name, field.name, field.type_ref));
+            continue;
+        };
+
+        // Convert the name into a Rust-friendly version
+        let mut rust_field_name = map_hungarian(&field.name);
+        if rust_type == "bool" && !rust_field_name.starts_with("is_") {
+            rust_field_name = format!("is_{}", rust_field_name);
+        }
+
+        if let Some(offset) = field.offset {
+            writeln!(buffer, "\t/// Offset: {:#x}.", offset)?;
+        }
+        writeln!(buffer,"\t#[serde(rename = \"{}\")]", field.name)?;
+
+        // If the field is a fixed array and is large, apply the BigArray attribute:
+        let big_array = fixed_array_len(&field.type_ref).is_some_and(|len| len > 32);
+        if big_array {
+            writeln!(buffer, "\t#[serde(with = \"serde_big_array::BigArray\")]")?;
+        }
+        writeln!(buffer, "\tpub {}: {},", rust_field_name, rust_type)?;
+        reflected.push(ReflectedField { field, rust_name: rust_field_name, big_array });
+    }
+
+    writeln!(buffer, "}}")?;
+    writeln!(buffer)?;
+
+    // Provide a ZVariant trait
+    writeln!(buffer, "{}::zvariant!({}, \"{}\");", module.types_crate, rust_class_name, variant_handle(original_class_name, false))?;
+    writeln!(buffer)?;
+
+    if let Some(kind) = math_kind(class) {
+        writeln!(buffer, "{}::math_conversions!({}, {});", module.types_crate, kind, rust_class_name)?;
+        writeln!(buffer)?;
+    }
+
+    generate_layout(&rust_class_name, class, &reflected, buffer)?;
+    if traits.plain {
+        generate_game_struct(class, &rust_class_name, &reflected, module, buffer)?;
+    }
+    generate_reflect(&rust_class_name, original_class_name, "Class", Some(class), &reflected, "", module, buffer)
+}
+
+/// The math classes glacier-types can convert to glam and mint, with their kind in `math_conversions!` and the
+/// type and names of their fields.
+const MATH_CLASSES: [(&str, &str, &str, &[&str]); 7] = [
+    ("SVector2", "vector2", "float32", &["x", "y"]),
+    ("SVector3", "vector3", "float32", &["x", "y", "z"]),
+    ("SVector4", "vector4", "float32", &["x", "y", "z", "w"]),
+    ("SQuaternion", "quaternion", "float32", &["x", "y", "z", "w"]),
+    ("SMatrix43", "matrix43", "SVector3", &["XAxis", "YAxis", "ZAxis", "Trans"]),
+    ("SColorRGB", "color_rgb", "float32", &["r", "g", "b"]),
+    ("SColorRGBA", "color_rgba", "float32", &["r", "g", "b", "a"]),
+];
+
+/// The kind of a math class, if it is declared with the fields the conversions expect.
+fn math_kind(class: &ClassDecl) -> Option<&'static str> {
+    let (_, kind, field_type, field_names) = MATH_CLASSES.iter().find(|(name, ..)| *name == class.name)?;
+    let matches = class.fields.len() == field_names.len()
+        && class.fields.iter().zip(field_names.iter())
+            .all(|(field, name)| field.name == *name && field.type_ref.name() == Some(field_type) && field.type_ref.args().is_empty());
+    matches.then_some(*kind)
+}
+
+/// The C++ primitives and built-in types `GameStruct` is implemented for.
+const PLAIN_PRIMITIVES: [&str; 17] = [
+    "bool", "int8", "uint8", "int16", "uint16", "int32", "int", "uint32", "unsigned", "unsigned int", "int64", "uint64",
+    "float32", "float", "float64", "double", "ZString",
+];
+
+/// Finds the plain classes: classes with a known layout made only of primitives, `ZString`s, fixed arrays, `TArray`s
+/// and other plain classes, which implement `GameStruct`. The plain classes of `shared` are found the same way
+/// glacier-types found them.
+fn plain_classes<'a>(model: &'a TypeModel, shared: Option<&'a TypeModel>) -> HashSet<&'a str> {
+    fn is_plain(type_ref: &TypeRef, plain: &HashSet<&str>) -> bool {
+        match type_ref {
+            TypeRef::Named { path, args } => match (path.as_slice(), args.as_slice()) {
+                ([name], []) => PLAIN_PRIMITIVES.contains(&name.as_str()) || plain.contains(name.as_str()),
+                ([name], [inner]) if name == "TArray" => is_plain(inner, plain),
+                ([name], [inner, TypeRef::Value(_)]) if name == "TFixedArray" => is_plain(inner, plain),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    let mut plain = shared.map(|shared| plain_classes(shared, None)).unwrap_or_default();
+    let shared_names = shared.map(|shared| shared.classes.iter().map(|class| class.name.as_str()).collect::<HashSet<_>>())
+        .unwrap_or_default();
+
+    // Classes can contain classes declared after them.
+    loop {
+        let found = model.classes.iter()
+            .filter(|class| !shared_names.contains(class.name.as_str()) && !plain.contains(class.name.as_str()))
+            .filter(|class| class.size.is_some() && class.alignment.is_some())
+            .filter(|class| class.fields.iter().all(|field| field.offset.is_some() && is_plain(&field.type_ref, &plain)))
+            .map(|class| class.name.as_str())
+            .collect::<Vec<_>>();
+        if found.is_empty() {
+            return plain;
+        }
+        plain.extend(found);
+    }
+}
+
+/// Finds the classes and enums that can't derive `Default`: enums without variants, and classes containing a fixed
+/// array longer than 32 elements or a type that can't derive it.
+fn types_without_default(model: &TypeModel) -> HashSet<&str> {
+    fn has_default(type_ref: &TypeRef, without_default: &HashSet<&str>) -> bool {
+        match (type_ref.name(), type_ref.args()) {
+            (Some("TArray" | "TMap"), _) => true,
+            (Some("TPair"), args) => args.iter().all(|arg| has_default(arg, without_default)),
+            (Some("TResourcePtr"), _) => !without_default.contains("ZRuntimeResourceID"),
+            (Some("TFixedArray"), [inner, TypeRef::Value(len)]) => *len <= 32 && has_default(inner, without_default),
+            (Some(name), _) => !without_default.contains(name),
+            (None, _) => true,
+        }
+    }
+
+    let mut without_default = model.enums.iter()
+        .filter(|enum_decl| enum_decl.variants.is_empty())
+        .map(|enum_decl| enum_decl.name.as_str())
+        .collect::<HashSet<_>>();
+    loop {
+        let found = model.classes.iter()
+            .filter(|class| !without_default.contains(class.name.as_str()))
+            .filter(|class| class.fields.iter().any(|field| !has_default(&field.type_ref, &without_default)))
+            .map(|class| class.name.as_str())
+            .collect::<Vec<_>>();
+        if found.is_empty() {
+            return without_default;
+        }
+        without_default.extend(found);
+    }
+}
+
+fn fixed_array_len(type_ref: &TypeRef) -> Option<i64> {
+    match (type_ref.name(), type_ref.args()) {
+        (Some("TFixedArray"), [_, TypeRef::Value(len)]) => Some(*len),
+        _ => None,
+    }
+}
+
+/// The types glacier-types implements by hand, by their C++ name.
+const HAND_WRITTEN_TYPES: [(&str, &str); 7] = [
+    ("ZString", "ZString"),
+    ("ZEncryptedString", "ZEncryptedString"),
+    ("ZHMArenas", "ZHMArenas"),
+    ("ZRepositoryID", "ZRepositoryId"),
+    ("TypeID", "TypeId"),
+    ("ZEntityRef", "ZEntityRef"),
+    ("ZVariant", "Box<dyn ZVariant>"),
+];
+
+/// Maps a C++ type to its Rust equivalent. Named types have to be implemented by hand or be in `declared`, the
+/// classes and enums of the type model. Returns `None` for types without a mapping.
+fn map_cpp_type_to_rust(type_ref: &TypeRef, declared: &HashSet<&str>) -> Option<String> {
+    let map = |type_ref| map_cpp_type_to_rust(type_ref, declared);
+    let (path, args) = match type_ref {
+        TypeRef::Named { path, args } => (path, args),
+        // Resources store the pointee in place of the pointer, or nothing for a null pointer.
+        TypeRef::Pointer(inner) => return Some(format!("Option<Box<{}>>", map(inner)?)),
+        TypeRef::Value(_) => return None,
+    };
+    let name = path.last()?.as_str();
+
+    let rust_type = match (name, args.as_slice()) {
+        ("float32" | "float", []) => "f32".to_owned(),
+        ("float64" | "double", []) => "f64".to_owned(),
+        ("int64", []) => "i64".to_owned(),
+        ("uint64", []) => "u64".to_owned(),
+        ("int32" | "int", []) => "i32".to_owned(),
+        ("uint32" | "unsigned" | "unsigned int", []) => "u32".to_owned(),
+        ("int16", []) => "i16".to_owned(),
+        ("uint16", []) => "u16".to_owned(),
+        ("int8", []) => "i8".to_owned(),
+        ("uint8", []) => "u8".to_owned(),
+        ("bool", []) => "bool".to_owned(),
+        ("char", []) => "char".to_owned(),
+        ("TArray", [inner]) => format!("Vec<{}>", map(inner)?),
+        ("TFixedArray", [inner, TypeRef::Value(len)]) => format!("[{}; {}]", map(inner)?, len),
+        ("TMap", [key, value]) => format!("std::collections::HashMap<{}, {}>", map(key)?, map(value)?),
+        // ResourceLib writes pairs as two element arrays, like serde does with tuples.
+        ("TPair", [first, second]) => format!("({}, {})", map(first)?, map(second)?),
+        // Resources reference other resources by their runtime ID.
+        ("TResourcePtr" | "ZResourcePtr", [] | [_]) if declared.contains("ZRuntimeResourceID") => "ZRuntimeResourceId".to_owned(),
+        ("TEntityRef", [_]) => "ZEntityRef".to_owned(),
+        (_, []) => {
+            if let Some((_, rust_type)) = HAND_WRITTEN_TYPES.iter().find(|(cpp_type, _)| *cpp_type == name) {
+                return Some(rust_type.to_string());
+            }
+            // Declarations are generated into a single flat module, nested types are declared as `Outer_Inner`.
+            let flattened = path.join("_");
+            let declared_name = [flattened.as_str(), name].into_iter().find(|candidate| declared.contains(candidate))?;
+            heck::AsUpperCamelCase(declared_name).to_string()
+        }
+        _ => return None,
+    };
+    Some(rust_type)
+}
+
+fn map_hungarian(var_name: &str) -> String {
+    // Hungarian notations like "m_" or "mValue"
+    let bytes = var_name.as_bytes();
+    let trimmed = if bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b'_' {
+        &var_name[2..]
+    } else {
+        var_name
+    };
+    let candidate = heck::AsSnakeCase(trimmed).to_string();
+    // Keywords and collisions
+    match candidate.as_str() {
+        "type" => "type_".to_string(),
+        "move" => "move_".to_string(),
+        _ => candidate,
+    }
+}
+
+fn remove_common_prefix_from_enum_variants(fields: &mut [(String, String, i64)]) {
+    if fields.is_empty() {
+        return;
+    }
+
+    let first_raw_name = &fields[0].0.to_owned();
+    let Some(underscore_pos) = first_raw_name.find('_') else {
+        return;
+    };
+
+    let candidate_prefix = &first_raw_name[..underscore_pos];
+
+    if fields.iter().all(|(raw_name, _, _)| raw_name.starts_with(candidate_prefix)) {
+        let prefix_upper = heck::AsUpperCamelCase(candidate_prefix).to_string();
+        for (raw_name, rust_name, _) in fields.iter_mut() {
+            if raw_name.starts_with(candidate_prefix) {
+                if let Some(stripped) = rust_name.strip_prefix(&prefix_upper) {
+                    // If we strip the prefix and the new name starts with a digit, revert.
+                    if stripped.chars().next().is_some_and(|c| c.is_numeric()) {
+                        continue;
+                    }
+                    *rust_name = stripped.to_owned();
+                }
+            }
+        }
+    }
+}
+
+/// The type name a class or enum is registered under as a `ZVariant`.
+pub(crate) fn variant_handle(original_name: &str, is_enum: bool) -> String {
+    if is_enum && original_name.contains("eParticleEmitterBoxEntity") {
+        original_name.to_owned()
+    } else {
+        replace_last(original_name, "_", ".")
+    }
+}
+
+fn replace_last(input: &str, pattern: &str, replacement: &str) -> String {
+    if let Some(pos) = input.rfind(pattern) {
+        let mut result = input.to_owned();
+        result.replace_range(pos..pos+pattern.len(), replacement);
+        result
+    } else {
+        input.to_owned()
+    }
+}
\ No newline at end of file
@@ -1,7 +1,188 @@
-// pub mod generated_structs;
-pub mod glacier_types;
+//! Generates the Rust bindings of the glacier-types and glacier-hm* crates from the `ZHMGen.h` headers of ResourceLib.
+//!
+//! This crate is only meant to be used from their build scripts. glacier-types parses the header of every version,
+//! generates the types that are identical in all of them and publishes the type models to the per-version crates,
+//! which generate the rest.
 
-pub mod hm2016_bindings;
-pub mod hm2_bindings;
-pub mod hm3_bindings;
+pub use glacier_ir as ir;
+pub mod common;
+pub mod generator;
+pub mod parser;
+pub mod reachability;
+pub mod schema;
 
+use std::{fs, io};
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use generator::Module;
+use ir::TypeModel;
+
+const RESOURCELIB_SRC: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../extern/ZHMTools/Libraries/ResourceLib");
+
+/// When set, the generated bindings are also copied to this directory for inspection.
+const DUMP_DIR_ENV: &str = "GLACIER_CODEGEN_DUMP_DIR";
+
+/// Extra classes or enums to generate in reachability mode, separated by commas.
+const EXTRA_TYPES_ENV: &str = "GLACIER_CODEGEN_EXTRA_TYPES";
+
+/// The links metadata key glacier-types publishes its `OUT_DIR` under. The per-version crates read the type models
+/// from there instead of parsing the headers again.
+const MODELS_DIR_ENV: &str = "DEP_GLACIER_TYPES_MODELS_DIR";
+
+/// The module the types shared by every version are generated into, inside glacier-types.
+const COMMON_MODULE: &str = "common";
+
+/// The game versions, as `(feature, header directory, module)`.
+pub const VERSIONS: [(&str, &str, &str); 3] = [
+    ("hm2016", "HM2016", "hm2016_bindings"),
+    ("hm2", "HM2", "hm2_bindings"),
+    ("hm3", "HM3", "hm3_bindings"),
+];
+
+/// The resource type features with the root class of the resource in each version, in the order of `VERSIONS`.
+pub const RESOURCE_ROOTS: [(&str, [&str; 3]); 9] = [
+    ("temp", ["STemplateEntity", "STemplateEntityFactory", "STemplateEntityFactory"]),
+    ("tblu", ["STemplateEntityBlueprint", "STemplateEntityBlueprint", "STemplateEntityBlueprint"]),
+    ("airg", ["SReasoningGrid", "SReasoningGrid", "SReasoningGrid"]),
+    ("atmd", ["ZamdTake", "ZamdTake", "ZamdTake"]),
+    ("vidb", ["SVideoDatabaseData", "SVideoDatabaseData", "SVideoDatabaseData"]),
+    ("cblu", ["SCppEntityBlueprint", "SCppEntityBlueprint", "SCppEntityBlueprint"]),
+    ("cppt", ["SCppEntity", "SCppEntity", "SCppEntity"]),
+    ("crmd", ["SCrowdMapData", "SCrowdMapData", "SCrowdMapData"]),
+    ("gidx", ["SResourceIndex", "SResourceIndex", "SResourceIndex"]),
+];
+
+/// Checks a feature of the crate whose build script is running.
+pub fn feature_enabled(feature: &str) -> bool {
+    std::env::var_os(format!("CARGO_FEATURE_{}", feature.to_uppercase().replace('-', "_"))).is_some()
+}
+
+/// The types to generate for a version, or `None` if the all-types feature is enabled.
+pub fn resource_roots(version_index: usize) -> Option<Vec<String>> {
+    if feature_enabled("all-types") {
+        return None;
+    }
+
+    let extra_types = std::env::var(EXTRA_TYPES_ENV).unwrap_or_default();
+    Some(RESOURCE_ROOTS.iter()
+        .filter(|(resource_feature, _)| feature_enabled(resource_feature))
+        .map(|(_, roots)| roots[version_index])
+        .chain(extra_types.split(',').map(str::trim).filter(|name| !name.is_empty()))
+        .map(str::to_owned)
+        .collect())
+}
+
+fn rerun_if_changed() {
+    println!("cargo:rerun-if-changed=build.rs"); //prevent this file from running every time
+    println!("cargo:rerun-if-env-changed=GLACIER_CODEGEN_CLANG_ARGS");
+    println!("cargo:rerun-if-env-changed={}", DUMP_DIR_ENV);
+    println!("cargo:rerun-if-env-changed={}", EXTRA_TYPES_ENV);
+}
+
+fn out_dir() -> PathBuf {
+    PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR is not set"))
+}
+
+/// The generator writes the model of every module it generates next to it, see `generator::generate_bindings`.
+fn model_path(dir: &Path, module_name: &str) -> PathBuf {
+    dir.join(format!("{}.json", module_name))
+}
+
+fn write_model(path: &Path, model: &TypeModel) -> io::Result<()> {
+    serde_json::to_writer(BufWriter::new(File::create(path)?), model)?;
+    Ok(())
+}
+
+fn read_model(path: &Path) -> io::Result<TypeModel> {
+    Ok(serde_json::from_reader(io::BufReader::new(File::open(path)?))?)
+}
+
+fn restrict(model: &TypeModel, roots: Option<&[String]>) -> TypeModel {
+    match roots {
+        Some(roots) => reachability::reachable_types(model, &roots.iter().map(String::as_str).collect::<Vec<_>>()),
+        None => model.clone(),
+    }
+}
+
+/// The build script of glacier-types.
+///
+/// Parses the header of every version, generates the types that are identical in all of them and writes the parsed
+/// models to `OUT_DIR` for the per-version crates.
+pub fn generate_common() -> io::Result<()> {
+    rerun_if_changed();
+    let out_dir = out_dir();
+
+    let mut models = Vec::new();
+    for (_, header_dir, module_name) in VERSIONS {
+        let header_path = Path::new(RESOURCELIB_SRC).join("Src/Generated").join(header_dir).join("ZHMGen.h");
+        println!("cargo:rerun-if-changed={}", header_path.display());
+        let include_dirs = [
+            header_path.parent().unwrap(),
+            &Path::new(RESOURCELIB_SRC).join("Src"),
+            &Path::new(RESOURCELIB_SRC).join("Include"),
+        ];
+        let model = parser::parse_header(&header_path, &include_dirs)
+            .unwrap_or_else(|e| panic!("Failed to parse {}: {}", header_path.display(), e));
+        write_model(&model_path(&out_dir, module_name), &model)?;
+        models.push(model);
+    }
+
+    // Resource roots stay per version, converting them with ResourceLib requires knowing their version.
+    let roots = RESOURCE_ROOTS.iter().flat_map(|(_, roots)| roots.iter().copied()).collect::<Vec<_>>();
+    let shared = common::shared_types(&models, &roots);
+
+    // Only generate the shared types that one of the versions needs.
+    let needed = models.iter().enumerate()
+        .map(|(version_index, model)| restrict(model, resource_roots(version_index).as_deref()))
+        .flat_map(|model| model.classes.into_iter().map(|class| class.name).chain(model.enums.into_iter().map(|enum_decl| enum_decl.name)))
+        .collect::<HashSet<_>>();
+    let shared = TypeModel {
+        classes: shared.classes.into_iter().filter(|class| needed.contains(&class.name)).collect(),
+        enums: shared.enums.into_iter().filter(|enum_decl| needed.contains(&enum_decl.name)).collect(),
+    };
+    generator::generate_bindings(&shared, &Module { name: COMMON_MODULE, path: "crate::common", types_crate: "crate", shared: None, resource_roots: &[] })?;
+    dump_bindings(&out_dir, COMMON_MODULE)?;
+
+    println!("cargo:models_dir={}", out_dir.display());
+    Ok(())
+}
+
+/// The build script of the crate of a single version, e.g. `generate_version("hm3")` for glacier-hm3.
+///
+/// Types generated by glacier-types are re-exported instead of generated again.
+pub fn generate_version(version: &str) -> io::Result<()> {
+    rerun_if_changed();
+    println!("cargo:rerun-if-env-changed={}", MODELS_DIR_ENV);
+
+    let (version_index, (_, _, module_name)) = VERSIONS.iter().enumerate()
+        .find(|(_, (feature, _, _))| *feature == version)
+        .unwrap_or_else(|| panic!("Unknown version {}", version));
+    let models_dir = PathBuf::from(std::env::var(MODELS_DIR_ENV).expect("glacier-types did not publish its type models"));
+
+    let model = restrict(&read_model(&model_path(&models_dir, module_name))?, resource_roots(version_index).as_deref());
+    let shared = read_model(&model_path(&models_dir, COMMON_MODULE))?;
+
+    // The resource types whose root class was generated.
+    let roots = RESOURCE_ROOTS.iter()
+        .map(|(resource_feature, roots)| (resource_feature.to_uppercase(), roots[version_index]))
+        .filter(|(_, root)| model.class(root).is_some())
+        .collect::<Vec<_>>();
+
+    generator::generate_bindings(&model, &Module { name: module_name, path: "crate", types_crate: "glacier_types", shared: Some(&shared), resource_roots: &roots })?;
+    dump_bindings(&out_dir(), module_name)
+}
+
+fn dump_bindings(out_dir: &Path, module_name: &str) -> io::Result<()> {
+    let Ok(dump_dir) = std::env::var(DUMP_DIR_ENV) else {
+        return Ok(());
+    };
+    let dump_dir = Path::new(&dump_dir).join(module_name);
+    fs::create_dir_all(&dump_dir)?;
+    for entry in fs::read_dir(out_dir.join(module_name))? {
+        let entry = entry?;
+        fs::copy(entry.path(), dump_dir.join(entry.file_name()))?;
+    }
+    Ok(())
+}
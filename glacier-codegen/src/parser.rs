@@ -0,0 +1,201 @@
+//! Extracts the type model from a `ZHMGen.h` header using libclang.
+
+use std::ffi::{c_int, CStr, CString};
+use std::os::raw::c_void;
+use std::path::Path;
+use std::ptr;
+use clang_sys::*;
+use crate::ir::{ClassDecl, EnumDecl, EnumVariant, FieldDecl, TypeModel, TypeRef};
+
+/// Extra arguments passed to clang, e.g. additional include directories for the headers ZHMGen.h includes.
+const CLANG_ARGS_ENV: &str = "GLACIER_CODEGEN_CLANG_ARGS";
+
+/// Parses a header and collects every class and enum declared in it. Declarations from included headers are
+/// skipped.
+///
+/// Any error reported by clang fails the parse: clang recovers from unknown types by treating them as `int`, which
+/// would otherwise silently end up in the generated bindings.
+pub fn parse_header<P: AsRef<Path>>(header_path: P, include_dirs: &[&Path]) -> Result<TypeModel, String> {
+    if !clang_sys::is_loaded() {
+        clang_sys::load()?;
+    }
+
+    // Sizes and offsets follow the MSVC x64 layout of the game and ResourceLib, whatever the host is.
+    let mut args = [
+        "-x", "c++", "-std=c++20", "--target=x86_64-pc-windows-msvc", "-fms-extensions", "-fms-compatibility",
+    ].map(str::to_owned).to_vec();
+    if let Some(search_paths) = clang_sys::support::Clang::find(None, &[]).and_then(|clang| clang.cpp_search_paths) {
+        args.extend(search_paths.iter().map(|path| format!("-isystem{}", path.display())));
+    }
+    args.extend(include_dirs.iter().map(|dir| format!("-I{}", dir.display())));
+    if let Ok(extra_args) = std::env::var(CLANG_ARGS_ENV) {
+        args.extend(extra_args.split_whitespace().map(str::to_owned));
+    }
+
+    let header = CString::new(header_path.as_ref().to_string_lossy().as_bytes()).map_err(|e| e.to_string())?;
+    let args = args.into_iter().map(CString::new).collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())?;
+    let arg_ptrs = args.iter().map(|arg| arg.as_ptr()).collect::<Vec<_>>();
+
+    unsafe {
+        let index = clang_createIndex(0, 0);
+        let unit = clang_parseTranslationUnit(
+            index,
+            header.as_ptr(),
+            arg_ptrs.as_ptr(),
+            arg_ptrs.len() as c_int,
+            ptr::null_mut(),
+            0,
+            CXTranslationUnit_SkipFunctionBodies,
+        );
+        if unit.is_null() {
+            clang_disposeIndex(index);
+            return Err(format!("clang could not parse {}", header_path.as_ref().display()));
+        }
+
+        let errors = errors(unit);
+        let result = if errors.is_empty() {
+            let mut model = TypeModel::default();
+            collect_declarations(clang_getTranslationUnitCursor(unit), None, &mut model).map(|_| model)
+        } else {
+            Err(errors.join("\n"))
+        };
+
+        clang_disposeTranslationUnit(unit);
+        clang_disposeIndex(index);
+        result
+    }
+}
+
+unsafe fn errors(unit: CXTranslationUnit) -> Vec<String> {
+    let mut errors = Vec::new();
+    for i in 0..clang_getNumDiagnostics(unit) {
+        let diagnostic = clang_getDiagnostic(unit, i);
+        if clang_getDiagnosticSeverity(diagnostic) >= CXDiagnostic_Error {
+            errors.push(to_string(clang_formatDiagnostic(diagnostic, clang_defaultDiagnosticDisplayOptions())));
+        }
+        clang_disposeDiagnostic(diagnostic);
+    }
+    errors
+}
+
+unsafe fn to_string(string: CXString) -> String {
+    let c_str = clang_getCString(string);
+    let result = if c_str.is_null() { String::new() } else { CStr::from_ptr(c_str).to_string_lossy().into_owned() };
+    clang_disposeString(string);
+    result
+}
+
+extern "C" fn push_child(cursor: CXCursor, _parent: CXCursor, data: CXClientData) -> CXChildVisitResult {
+    unsafe { (*(data as *mut Vec<CXCursor>)).push(cursor) };
+    CXChildVisit_Continue
+}
+
+unsafe fn children(cursor: CXCursor) -> Vec<CXCursor> {
+    let mut children = Vec::new();
+    clang_visitChildren(cursor, push_child, &mut children as *mut Vec<CXCursor> as *mut c_void);
+    children
+}
+
+/// Collects the classes and enums declared in `cursor`. Declarations nested in a class are named `Outer_Inner`, the
+/// way the generator and the types referencing them flatten `Outer::Inner`.
+unsafe fn collect_declarations(cursor: CXCursor, outer: Option<&str>, model: &mut TypeModel) -> Result<(), String> {
+    for child in children(cursor) {
+        if clang_Location_isFromMainFile(clang_getCursorLocation(child)) == 0 {
+            continue;
+        }
+        let kind = clang_getCursorKind(child);
+        let is_definition = clang_isCursorDefinition(child) != 0;
+        let name = || match outer {
+            Some(outer) => format!("{}_{}", outer, to_string(clang_getCursorSpelling(child))),
+            None => to_string(clang_getCursorSpelling(child)),
+        };
+        if kind == CXCursor_Namespace {
+            collect_declarations(child, outer, model)?;
+        } else if (kind == CXCursor_ClassDecl || kind == CXCursor_StructDecl) && is_definition {
+            let class = parse_class(child, name())?;
+            let class_name = class.name.clone();
+            model.classes.push(class);
+            collect_declarations(child, Some(&class_name), model)?;
+        } else if kind == CXCursor_EnumDecl && is_definition {
+            model.enums.push(parse_enum(child, name()));
+        }
+    }
+    Ok(())
+}
+
+fn non_negative(value: i64) -> Option<usize> {
+    usize::try_from(value).ok()
+}
+
+unsafe fn parse_class(cursor: CXCursor, name: String) -> Result<ClassDecl, String> {
+    let class_type = clang_getCursorType(cursor);
+
+    let mut fields = Vec::new();
+    for child in children(cursor) {
+        if clang_getCursorKind(child) != CXCursor_FieldDecl {
+            continue;
+        }
+        let field_name = to_string(clang_getCursorSpelling(child));
+        let spelling = to_string(clang_getTypeSpelling(clang_getCursorType(child)));
+        let type_ref = TypeRef::parse(&spelling).map_err(|e| format!("{}::{}: {}", name, field_name, e))?;
+        fields.push(FieldDecl {
+            name: field_name,
+            type_ref,
+            offset: non_negative(clang_Cursor_getOffsetOfField(child)).map(|bits| bits / 8),
+        });
+    }
+
+    // The declared alignment isn't applied by clang, the size is padded to it the way the game does.
+    let alignment = non_negative(clang_Type_getAlignOf(class_type))
+        .map(|alignment| declared_alignment(cursor).map_or(alignment, |declared| declared.max(alignment)));
+    let size = non_negative(clang_Type_getSizeOf(class_type))
+        .map(|size| alignment.map_or(size, |alignment| size.next_multiple_of(alignment)));
+
+    Ok(ClassDecl {
+        size,
+        alignment,
+        name,
+        fields,
+    })
+}
+
+/// The alignment ZHMGen.h declares in a comment, e.g. `class /*alignas(16)*/ SVector4`. Comments are only visible in
+/// the tokens of the declaration, up to the opening brace.
+unsafe fn declared_alignment(cursor: CXCursor) -> Option<usize> {
+    let unit = clang_Cursor_getTranslationUnit(cursor);
+    let mut tokens = ptr::null_mut();
+    let mut count = 0;
+    clang_tokenize(unit, clang_getCursorExtent(cursor), &mut tokens, &mut count);
+    if tokens.is_null() {
+        return None;
+    }
+
+    let alignment = std::slice::from_raw_parts(tokens, count as usize).iter()
+        .map(|&token| (clang_getTokenKind(token), to_string(clang_getTokenSpelling(unit, token))))
+        .take_while(|(kind, spelling)| !(*kind == CXToken_Punctuation && spelling == "{"))
+        .filter(|(kind, _)| *kind == CXToken_Comment)
+        .find_map(|(_, comment)| parse_alignas(&comment));
+    clang_disposeTokens(unit, tokens, count);
+    alignment
+}
+
+fn parse_alignas(comment: &str) -> Option<usize> {
+    let comment = comment.strip_prefix("/*")?.strip_suffix("*/")?.trim();
+    comment.strip_prefix("alignas(")?.strip_suffix(')')?.trim().parse().ok()
+}
+
+unsafe fn parse_enum(cursor: CXCursor, name: String) -> EnumDecl {
+    let variants = children(cursor).into_iter()
+        .filter(|&child| clang_getCursorKind(child) == CXCursor_EnumConstantDecl)
+        .map(|child| EnumVariant {
+            name: to_string(clang_getCursorSpelling(child)),
+            value: clang_getEnumConstantDeclValue(child),
+        })
+        .collect();
+
+    EnumDecl {
+        name,
+        underlying_type: to_string(clang_getTypeSpelling(clang_getEnumDeclIntegerType(cursor))),
+        variants,
+    }
+}
@@ -0,0 +1,65 @@
+//! Restricts a type model to the types transitively reachable from a set of root types.
+
+use std::collections::{HashSet, VecDeque};
+use crate::ir::{TypeModel, TypeRef};
+
+/// Collects the names of the declared types a type can refer to, including the ones its mapping needs.
+pub(crate) fn collect_names(type_ref: &TypeRef, names: &mut Vec<String>) {
+    match type_ref {
+        TypeRef::Named { path, args } => {
+            names.extend(path.last().cloned());
+            // Nested types are declared as `Outer_Inner`.
+            if path.len() > 1 {
+                names.push(path.join("_"));
+            }
+            // Resource pointers are mapped to runtime resource IDs.
+            if matches!(path.last().map(String::as_str), Some("TResourcePtr" | "ZResourcePtr")) {
+                names.push("ZRuntimeResourceID".to_owned());
+            }
+            args.iter().for_each(|arg| collect_names(arg, names));
+        }
+        TypeRef::Pointer(inner) => collect_names(inner, names),
+        TypeRef::Value(_) => {}
+    }
+}
+
+/// Keeps the classes and enums reachable from `roots` through the fields of classes, in their original order.
+///
+/// Every class and enum is registered as a `ZVariant` type, and the type of a value stored in one isn't known until it
+/// is read. If a `ZVariant` is reachable, the whole model is kept.
+pub fn reachable_types(model: &TypeModel, roots: &[&str]) -> TypeModel {
+    let mut reachable = HashSet::new();
+    let mut queue = VecDeque::new();
+    for &root in roots {
+        if model.class(root).is_none() && model.enum_decl(root).is_none() {
+            println!("cargo:warning=The root type {} does not exist in this version", root);
+        }
+        if reachable.insert(root.to_owned()) {
+            queue.push_back(root.to_owned());
+        }
+    }
+
+    while let Some(name) = queue.pop_front() {
+        let Some(class) = model.class(&name) else {
+            continue;
+        };
+        let mut names = Vec::new();
+        for field in &class.fields {
+            collect_names(&field.type_ref, &mut names);
+        }
+        for name in names {
+            if reachable.insert(name.clone()) {
+                queue.push_back(name);
+            }
+        }
+    }
+
+    if reachable.contains("ZVariant") {
+        return model.clone();
+    }
+
+    TypeModel {
+        classes: model.classes.iter().filter(|class| reachable.contains(class.name.as_str())).cloned().collect(),
+        enums: model.enums.iter().filter(|enum_decl| reachable.contains(enum_decl.name.as_str())).cloned().collect(),
+    }
+}
@@ -0,0 +1,161 @@
+//! Generates a JSON Schema of the JSON ResourceLib reads and writes for the classes and enums of a type model.
+//!
+//! Every class and enum is a definition in `$defs` under its C++ name, and `ZVariant` is a definition checking the
+//! `$val` of every `$type` the bindings know.
+
+use std::collections::HashSet;
+use serde_json::{json, Map, Value};
+use crate::generator::{variant_handle, BUILTIN_VARIANTS};
+use crate::ir::{ClassDecl, EnumDecl, TypeModel, TypeRef};
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Builds the schema of a model: a document with a definition for every class and enum and for `ZVariant`.
+pub fn json_schema(model: &TypeModel) -> Value {
+    let declared = model.classes.iter().map(|class| class.name.as_str())
+        .chain(model.enums.iter().map(|enum_decl| enum_decl.name.as_str()))
+        .collect::<HashSet<_>>();
+
+    let mut definitions = Map::new();
+    for class in &model.classes {
+        definitions.insert(class.name.clone(), class_schema(class, &declared));
+    }
+    for enum_decl in &model.enums {
+        definitions.insert(enum_decl.name.clone(), enum_schema(enum_decl));
+    }
+    definitions.insert("ZVariant".to_owned(), variant_schema(model, &declared));
+
+    json!({ "$schema": DRAFT, "$defs": definitions })
+}
+
+fn definition(name: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{}", name) })
+}
+
+fn class_schema(class: &ClassDecl, declared: &HashSet<&str>) -> Value {
+    let mut properties = Map::new();
+    for field in &class.fields {
+        // Written as a name or an ID, see `EntityTemplatePropertyId`.
+        let schema = if class.name == "SEntityTemplateProperty" && field.name == "nPropertyID" {
+            json!({ "type": ["string", "integer"] })
+        } else {
+            type_schema(&field.type_ref, declared)
+        };
+        properties.insert(field.name.clone(), schema);
+    }
+    let required = class.fields.iter().map(|field| field.name.as_str()).collect::<Vec<_>>();
+    json!({
+        "title": class.name,
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// Enums are written as the names of their values. Aliases are accepted as well, and with the `unknown-enum-values`
+/// feature so are the numbers and names ZHMGen.h doesn't declare, see `UnknownEnumValue`.
+fn enum_schema(enum_decl: &EnumDecl) -> Value {
+    let names = enum_decl.variants.iter().map(|variant| variant.name.as_str()).collect::<Vec<_>>();
+    if crate::feature_enabled("unknown-enum-values") {
+        json!({ "title": enum_decl.name, "anyOf": [{ "enum": names }, { "type": "integer" }, { "type": "string" }] })
+    } else {
+        json!({ "title": enum_decl.name, "enum": names })
+    }
+}
+
+/// A `ZVariant` is written as `{ "$type": ..., "$val": ... }`. The value of a type the bindings don't know isn't
+/// checked.
+fn variant_schema(model: &TypeModel, declared: &HashSet<&str>) -> Value {
+    let builtins = BUILTIN_VARIANTS.iter().map(|(type_name, _)| (type_name.to_string(), type_name.to_string()));
+    let classes = model.classes.iter().map(|class| (variant_handle(&class.name, false), class.name.clone()));
+    let enums = model.enums.iter().map(|enum_decl| (variant_handle(&enum_decl.name, true), enum_decl.name.clone()));
+
+    let mut cases = Vec::new();
+    for (type_name, cpp_type) in builtins.chain(classes).chain(enums) {
+        let value = match type_name.as_str() {
+            "void" => json!({ "type": "null" }),
+            _ => type_schema(&TypeRef::Named { path: vec![cpp_type], args: vec![] }, declared),
+        };
+        let array = json!({ "type": "array", "items": value });
+        for (type_name, value) in [(type_name.clone(), value), (format!("TArray<{}>", type_name), array)] {
+            cases.push(json!({
+                "if": { "properties": { "$type": { "const": type_name } } },
+                "then": { "properties": { "$val": value } },
+            }));
+        }
+    }
+
+    json!({
+        "title": "ZVariant",
+        "type": "object",
+        "properties": { "$type": { "type": "string" }, "$val": true },
+        "required": ["$type", "$val"],
+        "additionalProperties": false,
+        "allOf": cases,
+    })
+}
+
+fn integer(minimum: i128, maximum: i128) -> Value {
+    json!({ "type": "integer", "minimum": minimum, "maximum": maximum })
+}
+
+/// The schema of a C++ type, following `map_cpp_type_to_rust` and the serde implementations of glacier-types.
+/// Types without a mapping accept anything.
+fn type_schema(type_ref: &TypeRef, declared: &HashSet<&str>) -> Value {
+    let schema = |type_ref| type_schema(type_ref, declared);
+    let (path, args) = match type_ref {
+        TypeRef::Named { path, args } => (path, args),
+        TypeRef::Pointer(inner) => return json!({ "anyOf": [schema(inner), { "type": "null" }] }),
+        TypeRef::Value(_) => return json!(true),
+    };
+    let Some(name) = path.last().map(String::as_str) else {
+        return json!(true);
+    };
+
+    match (name, args.as_slice()) {
+        ("float32" | "float" | "float64" | "double", []) => json!({ "type": "number" }),
+        ("int64", []) => integer(i64::MIN.into(), i64::MAX.into()),
+        ("uint64", []) => integer(0, u64::MAX.into()),
+        ("int32" | "int", []) => integer(i32::MIN.into(), i32::MAX.into()),
+        ("uint32" | "unsigned" | "unsigned int", []) => integer(0, u32::MAX.into()),
+        ("int16", []) => integer(i16::MIN.into(), i16::MAX.into()),
+        ("uint16", []) => integer(0, u16::MAX.into()),
+        ("int8", []) => integer(i8::MIN.into(), i8::MAX.into()),
+        ("uint8", []) => integer(0, u8::MAX.into()),
+        ("bool", []) => json!({ "type": "boolean" }),
+        ("char", []) => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+        ("ZString" | "ZEncryptedString" | "String" | "TypeID", []) => json!({ "type": "string" }),
+        ("ZRepositoryID", []) => json!({
+            "type": "string",
+            "format": "uuid",
+            "pattern": "^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
+        }),
+        ("ZHMArenas", []) => json!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "m_Id": integer(0, u32::MAX.into()),
+                    "m_TypeIndex": integer(0, u32::MAX.into()),
+                    "m_ObjectCount": integer(0, u32::MAX.into()),
+                },
+                "required": ["m_Id", "m_TypeIndex", "m_ObjectCount"],
+                "additionalProperties": false,
+            },
+        }),
+        ("ZVariant", []) => definition("ZVariant"),
+        ("ZEntityRef", []) | ("TEntityRef", [_]) => json!({ "type": "null" }),
+        ("TArray", [inner]) => json!({ "type": "array", "items": schema(inner) }),
+        ("TFixedArray", [inner, TypeRef::Value(len)]) => json!({ "type": "array", "items": schema(inner), "minItems": len, "maxItems": len }),
+        ("TMap", [_, value]) => json!({ "type": "object", "additionalProperties": schema(value) }),
+        ("TPair", [first, second]) => json!({ "type": "array", "prefixItems": [schema(first), schema(second)], "items": false, "minItems": 2 }),
+        ("TResourcePtr" | "ZResourcePtr", [] | [_]) if declared.contains("ZRuntimeResourceID") => definition("ZRuntimeResourceID"),
+        (_, []) => {
+            let flattened = path.join("_");
+            let declared_name = [flattened.as_str(), name].into_iter().find(|candidate| declared.contains(candidate));
+            declared_name.map(definition).unwrap_or(json!(true))
+        }
+        _ => json!(true),
+    }
+}
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+use glacier_codegen::parser::parse_header;
+
+const HEADER: &str = r#"
+using uint8 = unsigned char;
+using float32 = float;
+
+class /*alignas(16)*/ SVector4
+{
+public:
+	float32 x;
+	float32 y;
+	float32 z;
+	float32 w;
+};
+
+class /*alignas(8)*/ SFlags
+{
+public:
+	uint8 nFlags; // 0x0
+};
+
+class SVector3
+{
+public:
+	float32 x;
+	float32 y;
+	float32 z;
+};
+"#;
+
+fn write_header(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("glacier-codegen-{}-{}.h", name, std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_declared_alignment() {
+    let path = write_header("alignment", HEADER);
+    let model = parse_header(&path, &[]).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let layout = |name: &str| model.class(name).map(|class| (class.size, class.alignment));
+    assert_eq!(layout("SVector4"), Some((Some(16), Some(16))));
+    assert_eq!(layout("SFlags"), Some((Some(8), Some(8))));
+    assert_eq!(layout("SVector3"), Some((Some(12), Some(4))));
+    assert_eq!(model.class("SFlags").unwrap().field("nFlags").unwrap().offset, Some(0));
+}
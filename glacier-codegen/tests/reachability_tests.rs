@@ -0,0 +1,43 @@
+use glacier_codegen::ir::{ClassDecl, EnumDecl, FieldDecl, TypeModel, TypeRef};
+use glacier_codegen::reachability::reachable_types;
+
+fn class(name: &str, fields: &[(&str, &str)]) -> ClassDecl {
+    ClassDecl {
+        name: name.to_owned(),
+        size: None,
+        alignment: None,
+        fields: fields.iter()
+            .map(|(name, type_ref)| FieldDecl { name: name.to_string(), type_ref: TypeRef::parse(type_ref).unwrap(), offset: None })
+            .collect(),
+    }
+}
+
+fn model() -> TypeModel {
+    TypeModel {
+        classes: vec![
+            class("SVector3", &[("x", "float32"), ("y", "float32"), ("z", "float32")]),
+            class("SReasoningGrid", &[("m_WaypointList", "TArray<SGWaypoint>")]),
+            class("SGWaypoint", &[("vPos", "SVector4")]),
+            class("SVector4", &[("x", "float32"), ("y", "float32"), ("z", "float32"), ("w", "float32")]),
+            class("SEntityTemplateProperty", &[("nPropertyID", "uint32"), ("value", "ZVariant")]),
+        ],
+        enums: vec![EnumDecl { name: "EActorType".to_owned(), underlying_type: "int".to_owned(), variants: vec![] }],
+    }
+}
+
+fn names(model: &TypeModel) -> Vec<&str> {
+    model.classes.iter().map(|class| class.name.as_str())
+        .chain(model.enums.iter().map(|enum_decl| enum_decl.name.as_str()))
+        .collect()
+}
+
+#[test]
+fn test_reachable_types() {
+    assert_eq!(names(&reachable_types(&model(), &["SReasoningGrid"])), ["SReasoningGrid", "SGWaypoint", "SVector4"]);
+}
+
+#[test]
+fn test_variants_keep_every_type() {
+    // Any type can be stored in the `ZVariant` of a property.
+    assert_eq!(reachable_types(&model(), &["SEntityTemplateProperty"]), model());
+}
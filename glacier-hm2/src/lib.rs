@@ -0,0 +1,6 @@
+//! The types of HITMAN 2, generated from its ZHMGen.h by glacier-codegen.
+//!
+//! Types declared identically by every version are defined in glacier-types and re-exported from `enums` and
+//! `properties`, so they can be passed between the versions as they are.
+
+include!(concat!(env!("OUT_DIR"), "/hm2_bindings/mod.rs"));
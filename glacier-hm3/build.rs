@@ -0,0 +1,3 @@
+fn main() {
+    glacier_codegen::generate_version("hm3").unwrap_or_else(|error| panic!("{}", error));
+}
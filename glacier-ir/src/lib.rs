@@ -0,0 +1,209 @@
+//! The intermediate representation of the type system of a game version, extracted from its `ZHMGen.h` header.
+//!
+//! glacier-codegen generates the Rust bindings from this model and writes it as JSON to `OUT_DIR`. Each bindings
+//! crate exposes it again at runtime through `type_model()`, and glacier-types re-exports this crate as
+//! `glacier_types::ir`.
+
+use std::fmt::{Display, Formatter};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TypeModel {
+    pub classes: Vec<ClassDecl>,
+    pub enums: Vec<EnumDecl>,
+}
+
+impl TypeModel {
+    pub fn class(&self, name: &str) -> Option<&ClassDecl> {
+        self.classes.iter().find(|class| class.name == name)
+    }
+
+    pub fn enum_decl(&self, name: &str) -> Option<&EnumDecl> {
+        self.enums.iter().find(|enum_decl| enum_decl.name == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassDecl {
+    pub name: String,
+    pub size: Option<usize>,
+    /// The alignment in bytes, at least the one ZHMGen.h declares in an `alignas` comment.
+    pub alignment: Option<usize>,
+    pub fields: Vec<FieldDecl>,
+}
+
+impl ClassDecl {
+    pub fn field(&self, name: &str) -> Option<&FieldDecl> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDecl {
+    pub name: String,
+    pub type_ref: TypeRef,
+    /// The offset of the field in bytes.
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnumDecl {
+    pub name: String,
+    pub underlying_type: String,
+    pub variants: Vec<EnumVariant>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnumVariant {
+    pub name: String,
+    pub value: i64,
+}
+
+/// A C++ type as written in the header, e.g. `TArray<TPair<ZString, int32>>` or `STypeID*`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeRef {
+    /// A possibly namespaced and templated type.
+    Named { path: Vec<String>, args: Vec<TypeRef> },
+    /// An integral template argument, like the length of a `TFixedArray`.
+    Value(i64),
+    Pointer(Box<TypeRef>),
+}
+
+impl TypeRef {
+    /// The unqualified name of a named type.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            TypeRef::Named { path, .. } => path.last().map(String::as_str),
+            _ => None,
+        }
+    }
+
+    pub fn args(&self) -> &[TypeRef] {
+        match self {
+            TypeRef::Named { args, .. } => args,
+            _ => &[],
+        }
+    }
+
+    /// Parses a type spelling as reported by clang.
+    pub fn parse(spelling: &str) -> Result<TypeRef, String> {
+        let tokens = tokenize(spelling)?;
+        let mut position = 0;
+        let type_ref = parse_type(&tokens, &mut position)?;
+        if position != tokens.len() {
+            return Err(format!("unexpected `{}` in type `{}`", tokens[position], spelling));
+        }
+        Ok(type_ref)
+    }
+}
+
+impl Display for TypeRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeRef::Named { path, args } => {
+                write!(f, "{}", path.join("::"))?;
+                if !args.is_empty() {
+                    let args = args.iter().map(ToString::to_string).collect::<Vec<_>>();
+                    write!(f, "<{}>", args.join(", "))?;
+                }
+                Ok(())
+            }
+            TypeRef::Value(value) => write!(f, "{}", value),
+            TypeRef::Pointer(inner) => write!(f, "{}*", inner),
+        }
+    }
+}
+
+const QUALIFIERS: [&str; 5] = ["const", "volatile", "class", "struct", "enum"];
+const MULTI_WORD_TYPES: [&str; 5] = ["unsigned", "signed", "long", "short", "int"];
+
+fn tokenize(spelling: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = spelling.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_alphanumeric() || c == '_' || c == '-' {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_' || (**c == '-' && word.is_empty())) {
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(word);
+        } else if c == ':' {
+            chars.next();
+            if chars.next() != Some(':') {
+                return Err(format!("unexpected `:` in type `{}`", spelling));
+            }
+            tokens.push("::".to_owned());
+        } else if "<>,*&".contains(c) {
+            tokens.push(c.to_string());
+            chars.next();
+        } else {
+            return Err(format!("unexpected `{}` in type `{}`", c, spelling));
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_type(tokens: &[String], position: &mut usize) -> Result<TypeRef, String> {
+    let token = |position: usize| tokens.get(position).map(String::as_str);
+
+    while token(*position).is_some_and(|token| QUALIFIERS.contains(&token)) {
+        *position += 1;
+    }
+
+    if let Some(value) = token(*position).and_then(|token| token.parse::<i64>().ok()) {
+        *position += 1;
+        return Ok(TypeRef::Value(value));
+    }
+
+    let mut path = Vec::new();
+    loop {
+        let segment = token(*position).filter(|token| token.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_'))
+            .ok_or_else(|| format!("expected a type name, found `{}`", token(*position).unwrap_or("end of type")))?;
+        let mut segment = segment.to_owned();
+        *position += 1;
+        if MULTI_WORD_TYPES.contains(&segment.as_str()) {
+            while let Some(word) = token(*position).filter(|token| MULTI_WORD_TYPES.contains(token)) {
+                segment = format!("{} {}", segment, word);
+                *position += 1;
+            }
+        }
+        path.push(segment);
+        if token(*position) != Some("::") {
+            break;
+        }
+        *position += 1;
+    }
+
+    let mut args = Vec::new();
+    if token(*position) == Some("<") {
+        *position += 1;
+        loop {
+            args.push(parse_type(tokens, position)?);
+            match token(*position) {
+                Some(",") => *position += 1,
+                Some(">") => {
+                    *position += 1;
+                    break;
+                }
+                other => return Err(format!("expected `,` or `>`, found `{}`", other.unwrap_or("end of type"))),
+            }
+        }
+    }
+
+    let mut type_ref = TypeRef::Named { path, args };
+    while token(*position).is_some_and(|token| QUALIFIERS.contains(&token)) {
+        *position += 1;
+    }
+    while matches!(token(*position), Some("*") | Some("&")) {
+        type_ref = TypeRef::Pointer(Box::new(type_ref));
+        *position += 1;
+        while token(*position).is_some_and(|token| QUALIFIERS.contains(&token)) {
+            *position += 1;
+        }
+    }
+    Ok(type_ref)
+}
@@ -0,0 +1,3 @@
+fn main() {
+    glacier_codegen::generate_common().unwrap_or_else(|error| panic!("{}", error));
+}
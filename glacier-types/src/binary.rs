@@ -0,0 +1,330 @@
+//! Reads and writes plain game structs in the memory layout of the game, without ResourceLib.
+//!
+//! glacier-codegen implements [`GameStruct`] for every class made only of primitives, `ZString`s, fixed arrays,
+//! `TArray`s (`Vec`s in the generated classes) and other such classes, using the layout of ZHMGen.h. Pointers are
+//! stored as offsets from the start of the buffer; the writer records where it wrote them, so they can be relocated
+//! to addresses. How null pointers and arrays are stored depends on the [`Format`].
+
+use thiserror::Error;
+use crate::ZString;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BinaryError {
+    #[error("Reading {len} bytes at {offset:#x} is out of bounds of {size} bytes")]
+    OutOfBounds { offset: usize, len: usize, size: usize },
+
+    #[error("Invalid pointer {pointer:#x} at {offset:#x}")]
+    InvalidPointer { offset: usize, pointer: u64 },
+
+    #[error("Invalid array at {offset:#x}")]
+    InvalidArray { offset: usize },
+
+    #[error("Invalid string at {offset:#x}")]
+    InvalidString { offset: usize },
+}
+
+/// How pointers and arrays are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Format {
+    /// The memory of the game: null pointers are 0.
+    #[default]
+    Memory,
+    /// The data of resource files: null pointers are -1, and the elements of arrays and the characters of strings are
+    /// preceded by their count as a `uint32`.
+    Resource,
+}
+
+impl Format {
+    fn null_pointer(self) -> u64 {
+        match self {
+            Format::Memory => 0,
+            Format::Resource => u64::MAX,
+        }
+    }
+}
+
+/// A type with a fixed size and alignment in the game, that can be read from and written to its memory layout.
+pub trait GameStruct: Sized {
+    const SIZE: usize;
+    const ALIGN: usize;
+
+    fn read(reader: &BinaryReader, offset: usize) -> Result<Self, BinaryError>;
+
+    /// Writes the value to `offset`, which has to be allocated already.
+    fn write(&self, writer: &mut BinaryWriter, offset: usize);
+}
+
+/// Reads game structs from a buffer.
+pub struct BinaryReader<'a> {
+    data: &'a [u8],
+    base: u64,
+    format: Format,
+}
+
+impl<'a> BinaryReader<'a> {
+    /// A reader for a buffer whose pointers are offsets from its start.
+    pub fn new(data: &'a [u8]) -> Self {
+        BinaryReader { data, base: 0, format: Format::Memory }
+    }
+
+    /// A reader for a buffer that was loaded at `base`, e.g. a copy of the memory of the game.
+    pub fn with_base(data: &'a [u8], base: u64) -> Self {
+        BinaryReader { data, base, format: Format::Memory }
+    }
+
+    /// A reader for the data of a resource file.
+    pub fn resource(data: &'a [u8]) -> Self {
+        BinaryReader { data, base: 0, format: Format::Resource }
+    }
+
+    pub fn read<T: GameStruct>(&self, offset: usize) -> Result<T, BinaryError> {
+        T::read(self, offset)
+    }
+
+    pub fn bytes(&self, offset: usize, len: usize) -> Result<&'a [u8], BinaryError> {
+        offset.checked_add(len)
+            .and_then(|end| self.data.get(offset..end))
+            .ok_or(BinaryError::OutOfBounds { offset, len, size: self.data.len() })
+    }
+
+    /// Reads a pointer as an offset into the buffer, or `None` for a null pointer.
+    pub fn read_pointer(&self, offset: usize) -> Result<Option<usize>, BinaryError> {
+        let pointer = self.read::<u64>(offset)?;
+        if pointer == self.format.null_pointer() {
+            return Ok(None);
+        }
+        pointer.checked_sub(self.base)
+            .and_then(|target| usize::try_from(target).ok())
+            .filter(|&target| target <= self.data.len())
+            .map(Some)
+            .ok_or(BinaryError::InvalidPointer { offset, pointer })
+    }
+}
+
+/// Writes game structs to a growing buffer, allocating the elements of arrays after the structs containing them.
+#[derive(Debug, Default)]
+pub struct BinaryWriter {
+    data: Vec<u8>,
+    format: Format,
+    alignment: usize,
+    relocations: Vec<usize>,
+    runtime_resource_ids: Vec<usize>,
+}
+
+impl BinaryWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A writer for the data of a resource file.
+    pub fn resource() -> Self {
+        BinaryWriter { format: Format::Resource, ..Self::default() }
+    }
+
+    /// Reserves zeroed space at the end of the buffer and returns its offset.
+    pub fn allocate(&mut self, size: usize, alignment: usize) -> usize {
+        let alignment = alignment.max(1);
+        self.alignment = self.alignment.max(alignment);
+        let offset = self.data.len().next_multiple_of(alignment);
+        self.data.resize(offset + size, 0);
+        offset
+    }
+
+    /// Reserves space for the `size` bytes of `count` elements and returns the offset of the first one, preceded by
+    /// the count in the resource format.
+    pub fn allocate_array(&mut self, count: usize, size: usize, alignment: usize) -> usize {
+        if self.format == Format::Memory {
+            return self.allocate(size, alignment);
+        }
+        let alignment = alignment.max(4);
+        self.alignment = self.alignment.max(alignment);
+        let offset = (self.data.len() + 4).next_multiple_of(alignment);
+        self.data.resize(offset + size, 0);
+        self.write_bytes(offset - 4, &(count as u32).to_le_bytes());
+        offset
+    }
+
+    /// Allocates space for a value and writes it there.
+    pub fn append<T: GameStruct>(&mut self, value: &T) -> usize {
+        let offset = self.allocate(T::SIZE, T::ALIGN);
+        value.write(self, offset);
+        offset
+    }
+
+    pub fn write_bytes(&mut self, offset: usize, bytes: &[u8]) {
+        self.data[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+
+    /// Writes a pointer to another offset of the buffer and records it as a relocation.
+    pub fn write_pointer(&mut self, offset: usize, target: Option<usize>) {
+        match target {
+            Some(target) => {
+                self.write_bytes(offset, &(target as u64).to_le_bytes());
+                self.relocations.push(offset);
+            }
+            None => self.write_bytes(offset, &self.format.null_pointer().to_le_bytes()),
+        }
+    }
+
+    /// Records a `ZRuntimeResourceID` written at `offset`. Resource files list them for the engine to resolve.
+    pub fn register_runtime_resource_id(&mut self, offset: usize) {
+        self.runtime_resource_ids.push(offset);
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The offsets of the pointers in the buffer, in the order they were written.
+    pub fn relocations(&self) -> &[usize] {
+        &self.relocations
+    }
+
+    /// The offsets of the `ZRuntimeResourceID`s in the buffer, in the order they were written.
+    pub fn runtime_resource_ids(&self) -> &[usize] {
+        &self.runtime_resource_ids
+    }
+
+    /// The largest alignment of anything written so far.
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    pub fn into_parts(self) -> (Vec<u8>, Vec<usize>) {
+        (self.data, self.relocations)
+    }
+}
+
+/// Reads a game struct from the start of a buffer whose pointers are offsets from its start.
+pub fn from_bytes<T: GameStruct>(data: &[u8]) -> Result<T, BinaryError> {
+    BinaryReader::new(data).read(0)
+}
+
+/// Writes a game struct to the start of a new buffer, returning the buffer and the offsets of its pointers.
+pub fn to_bytes<T: GameStruct>(value: &T) -> (Vec<u8>, Vec<usize>) {
+    let mut writer = BinaryWriter::new();
+    writer.append(value);
+    writer.into_parts()
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty),*) => {
+        $(
+        impl GameStruct for $ty {
+            const SIZE: usize = size_of::<$ty>();
+            const ALIGN: usize = size_of::<$ty>();
+
+            fn read(reader: &BinaryReader, offset: usize) -> Result<Self, BinaryError> {
+                let bytes = reader.bytes(offset, Self::SIZE)?;
+                Ok(<$ty>::from_le_bytes(bytes.try_into().unwrap()))
+            }
+
+            fn write(&self, writer: &mut BinaryWriter, offset: usize) {
+                writer.write_bytes(offset, &self.to_le_bytes());
+            }
+        }
+        )*
+    };
+}
+
+impl_primitive!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
+impl GameStruct for bool {
+    const SIZE: usize = 1;
+    const ALIGN: usize = 1;
+
+    fn read(reader: &BinaryReader, offset: usize) -> Result<Self, BinaryError> {
+        Ok(reader.read::<u8>(offset)? != 0)
+    }
+
+    fn write(&self, writer: &mut BinaryWriter, offset: usize) {
+        writer.write_bytes(offset, &[u8::from(*self)]);
+    }
+}
+
+impl<T: GameStruct, const N: usize> GameStruct for [T; N] {
+    const SIZE: usize = T::SIZE * N;
+    const ALIGN: usize = T::ALIGN;
+
+    fn read(reader: &BinaryReader, offset: usize) -> Result<Self, BinaryError> {
+        let elements = (0..N).map(|index| reader.read(offset + index * T::SIZE)).collect::<Result<Vec<T>, _>>()?;
+        Ok(elements.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+
+    fn write(&self, writer: &mut BinaryWriter, offset: usize) {
+        for (index, element) in self.iter().enumerate() {
+            element.write(writer, offset + index * T::SIZE);
+        }
+    }
+}
+
+/// The layout of a `TArray`: pointers to its first element, one past its last element and the end of its allocation.
+impl<T: GameStruct> GameStruct for Vec<T> {
+    const SIZE: usize = 24;
+    const ALIGN: usize = 8;
+
+    fn read(reader: &BinaryReader, offset: usize) -> Result<Self, BinaryError> {
+        let (Some(begin), Some(end)) = (reader.read_pointer(offset)?, reader.read_pointer(offset + 8)?) else {
+            return Ok(Vec::new());
+        };
+        if T::SIZE == 0 {
+            // The pointers of elements without size can't tell how many there are, only the count of the resource
+            // format can.
+            if end != begin || reader.format == Format::Memory {
+                return Err(BinaryError::InvalidArray { offset });
+            }
+            let count = reader.read::<u32>(begin.checked_sub(4).ok_or(BinaryError::InvalidArray { offset })?)?;
+            return (0..count).map(|_| reader.read(begin)).collect();
+        }
+        if end < begin || (end - begin) % T::SIZE != 0 {
+            return Err(BinaryError::InvalidArray { offset });
+        }
+        (0..(end - begin) / T::SIZE)
+            .map(|index| reader.read(begin + index * T::SIZE))
+            .collect()
+    }
+
+    fn write(&self, writer: &mut BinaryWriter, offset: usize) {
+        if self.is_empty() {
+            for pointer in 0..3 {
+                writer.write_pointer(offset + pointer * 8, None);
+            }
+            return;
+        }
+
+        let begin = writer.allocate_array(self.len(), self.len() * T::SIZE, T::ALIGN);
+        for (index, element) in self.iter().enumerate() {
+            element.write(writer, begin + index * T::SIZE);
+        }
+        let end = begin + self.len() * T::SIZE;
+        writer.write_pointer(offset, Some(begin));
+        writer.write_pointer(offset + 8, Some(end));
+        writer.write_pointer(offset + 16, Some(end));
+    }
+}
+
+/// The length of a `ZString` in its lower 30 bits, with a flag ResourceLib sets on the strings it allocates, followed
+/// by a pointer to its null-terminated characters.
+impl GameStruct for ZString {
+    const SIZE: usize = 16;
+    const ALIGN: usize = 8;
+
+    fn read(reader: &BinaryReader, offset: usize) -> Result<Self, BinaryError> {
+        let length = reader.read::<u32>(offset)? & 0x3FFF_FFFF;
+        let Some(chars) = reader.read_pointer(offset + 8)? else {
+            return Ok(ZString::default());
+        };
+        let bytes = reader.bytes(chars, length as usize)?;
+        std::str::from_utf8(bytes)
+            .map(ZString::from)
+            .map_err(|_| BinaryError::InvalidString { offset })
+    }
+
+    fn write(&self, writer: &mut BinaryWriter, offset: usize) {
+        let bytes = self.as_str().as_bytes();
+        let chars = writer.allocate_array(bytes.len(), bytes.len() + 1, 8);
+        writer.write_bytes(chars, bytes);
+        writer.write_bytes(offset, &(bytes.len() as u32 | 0x4000_0000).to_le_bytes());
+        writer.write_pointer(offset + 8, Some(chars));
+    }
+}
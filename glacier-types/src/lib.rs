@@ -0,0 +1,378 @@
+//! The types shared by every game version: `ZVariant`, `TArray`, `ZString` and the other engine types that need a
+//! hand-written Rust equivalent, plus the generated classes and enums that every version declares identically.
+
+pub mod binary;
+pub mod math;
+pub mod reflect;
+
+pub use glacier_ir as ir;
+
+/// The classes and enums declared identically by every version, generated by glacier-codegen. The crates of the
+/// versions re-export them, so `glacier_hm2::properties::SVector3` and `glacier_hm3::properties::SVector3` are the
+/// same type.
+pub mod common {
+    include!(concat!(env!("OUT_DIR"), "/common/mod.rs"));
+}
+
+#[cfg(feature = "glam")]
+pub use glam;
+#[cfg(feature = "mint")]
+pub use mint;
+
+use std::fmt::Debug;
+use downcast_rs::Downcast;
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use erased_serde;
+    pub use inventory;
+
+    /// The deserializer typetag registers for every `ZVariant` implementation.
+    pub type DeserializeFn = fn(&mut dyn erased_serde::Deserializer) -> erased_serde::Result<Box<dyn crate::ZVariant>>;
+}
+
+/// Implements `ZVariant` for a type and for `TArray`s of it, under the type name ResourceLib uses.
+///
+/// The crates of the versions can't implement `ZVariant` for `TArray`s of their own types because of the orphan rule,
+/// so `TArray` implements it for every `ArrayElement` and this macro registers the deserializer of the `TArray` with
+/// typetag's registry itself. The calling crate has to depend on typetag.
+#[macro_export]
+macro_rules! zvariant {
+    ($ty:ty, $type_label:literal) => {
+        #[typetag::serde(name = $type_label)]
+        impl $crate::ZVariant for $ty {}
+
+        impl $crate::ArrayElement for $ty {
+            const ARRAY_TYPE_NAME: &'static str = concat!("TArray<", $type_label, ">");
+        }
+
+        $crate::__private::inventory::submit! {
+            <dyn $crate::ZVariant>::typetag_register(
+                concat!("TArray<", $type_label, ">"),
+                (|deserializer| Ok(Box::new($crate::__private::erased_serde::deserialize::<$crate::TArray<$ty>>(deserializer)?)))
+                    as $crate::__private::DeserializeFn,
+            )
+        }
+    };
+}
+
+#[derive(Default, PartialEq, Eq, Hash, Debug, Clone, Serialize, Deserialize)]
+pub struct ZString(String);
+
+impl ZString {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for ZString {
+    fn from(value: String) -> Self {
+        ZString(value)
+    }
+}
+
+impl From<&str> for ZString {
+    fn from(value: &str) -> Self {
+        ZString(value.to_owned())
+    }
+}
+
+impl std::fmt::Display for ZString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+zvariant!(ZString, "ZString");
+
+/// A string the game stores encrypted. ResourceLib writes the decrypted text.
+#[derive(Default, PartialEq, Eq, Hash, Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ZEncryptedString(pub String);
+
+impl From<&str> for ZEncryptedString {
+    fn from(value: &str) -> Self {
+        ZEncryptedString(value.to_owned())
+    }
+}
+
+zvariant!(ZEncryptedString, "ZEncryptedString");
+
+/// A memory arena of a resource: the type of the objects it holds and how many of them there are.
+#[derive(Default, PartialEq, Eq, Hash, Debug, Clone, Serialize, Deserialize)]
+pub struct ZHMArena {
+    #[serde(rename = "m_Id")]
+    pub id: u32,
+    #[serde(rename = "m_TypeIndex")]
+    pub type_index: u32,
+    #[serde(rename = "m_ObjectCount")]
+    pub object_count: u32,
+}
+
+/// The memory arenas of a resource, written by ResourceLib as a list of [`ZHMArena`]s.
+#[derive(Default, PartialEq, Eq, Hash, Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ZHMArenas(pub Vec<ZHMArena>);
+
+zvariant!(ZHMArenas, "ZHMArenas");
+
+/// A `TEntityRef<T>`, a reference to an entity that only exists at runtime. ResourceLib writes it as `null`.
+#[derive(Default, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct ZEntityRef;
+
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EntityTemplatePropertyId {
+    Str(String),
+    Num(u32),
+}
+
+impl Default for EntityTemplatePropertyId {
+    fn default() -> Self {
+        EntityTemplatePropertyId::Num(0)
+    }
+}
+
+/// A value of a generated enum that ZHMGen.h doesn't declare, e.g. one added by a newer patch of the game. Generated
+/// enums only have a variant holding it with the `unknown-enum-values` feature.
+///
+/// Numbers are kept as numbers, even when they're the value of a declared variant.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum UnknownEnumValue {
+    Value(i64),
+    Name(String),
+}
+
+#[derive(Default, PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct TArray<T: ZVariant>(Vec<T>);
+
+impl<T: ZVariant> TArray<T> {
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T: ZVariant> From<Vec<T>> for TArray<T> {
+    fn from(value: Vec<T>) -> Self {
+        TArray(value)
+    }
+}
+
+/// A type that can be stored in a `TArray` inside a `ZVariant`. Implemented by [`zvariant!`].
+pub trait ArrayElement: ZVariant + Clone + PartialEq + Serialize + DeserializeOwned {
+    /// The type name of `TArray<Self>`, e.g. `TArray<SMatrix43>`.
+    const ARRAY_TYPE_NAME: &'static str;
+}
+
+// Implemented by hand, typetag doesn't support generic impls. `zvariant!` registers the deserializer of every `TArray`.
+impl<T: ArrayElement> ZVariant for TArray<T> {
+    #[doc(hidden)]
+    fn typetag_name(&self) -> &'static str {
+        T::ARRAY_TYPE_NAME
+    }
+
+    #[doc(hidden)]
+    fn typetag_deserialize(&self) {}
+}
+
+
+/// The UUID of an entry in the repository, written as `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` by ResourceLib.
+#[derive(Default, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Clone, Copy)]
+pub struct ZRepositoryId(pub u128);
+
+impl std::fmt::Display for ZRepositoryId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hex = format!("{:032x}", self.0);
+        write!(f, "{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+    }
+}
+
+impl std::str::FromStr for ZRepositoryId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let groups = s.split('-').map(str::len).collect::<Vec<_>>();
+        if groups != [8, 4, 4, 4, 12] || !s.chars().all(|c| c == '-' || c.is_ascii_hexdigit()) {
+            return Err(format!("`{}` is not a UUID", s));
+        }
+        u128::from_str_radix(&s.replace('-', ""), 16)
+            .map(ZRepositoryId)
+            .map_err(|error| error.to_string())
+    }
+}
+
+impl Serialize for ZRepositoryId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ZRepositoryId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+zvariant!(ZRepositoryId, "ZRepositoryID");
+
+#[typetag::serde(tag = "$type", content = "$val")]
+pub trait ZVariant : Debug + Downcast + DynZVariant
+{}
+
+macro_rules! impl_variant_accessors {
+    ($($method:ident: $ty:ty),* $(,)?) => {
+        $(
+            #[doc = concat!("The value if it's a `", stringify!($ty), "`. Other numeric types aren't converted.")]
+            pub fn $method(&self) -> Option<$ty> {
+                self.get::<$ty>().copied()
+            }
+        )*
+    };
+}
+
+impl dyn ZVariant {
+    /// Boxes a value, e.g. `<dyn ZVariant>::new(SVector3 { x: 1.0, y: 2.0, z: 3.0 })`.
+    pub fn new<T: ZVariant>(value: T) -> Box<Self> {
+        Box::new(value)
+    }
+
+    /// The type name ResourceLib uses for the value, e.g. `TArray<SVector3>`.
+    pub fn type_name(&self) -> &'static str {
+        self.typetag_name()
+    }
+
+    /// The value if it's a `T`.
+    pub fn get<T: ZVariant>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+
+    /// The value if it's a `T`.
+    pub fn get_mut<T: ZVariant>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut::<T>()
+    }
+
+    impl_variant_accessors! {
+        as_bool: bool,
+        as_i8: i8,
+        as_i16: i16,
+        as_i32: i32,
+        as_i64: i64,
+        as_u8: u8,
+        as_u16: u16,
+        as_u32: u32,
+        as_u64: u64,
+        as_f32: f32,
+        as_f64: f64,
+    }
+
+    /// The text of a `ZString`, `String` or `ZEncryptedString`.
+    pub fn as_str(&self) -> Option<&str> {
+        self.get::<ZString>().map(ZString::as_str)
+            .or_else(|| self.get::<String>().map(String::as_str))
+            .or_else(|| self.get::<ZEncryptedString>().map(|string| string.0.as_str()))
+    }
+
+    /// The elements of a `TArray<T>`.
+    pub fn as_array<T: ArrayElement>(&self) -> Option<&[T]> {
+        self.get::<TArray<T>>().map(TArray::as_slice)
+    }
+
+    pub fn is_void(&self) -> bool {
+        self.get::<Empty>().is_some()
+    }
+}
+
+/// Object safe `Clone` and `PartialEq` for `ZVariant`s, implemented for every `ZVariant` that is `Clone + PartialEq`.
+pub trait DynZVariant {
+    fn clone_variant(&self) -> Box<dyn ZVariant>;
+
+    /// Whether `other` has the same type and value.
+    fn eq_variant(&self, other: &dyn ZVariant) -> bool;
+}
+
+impl<T: ZVariant + Clone + PartialEq> DynZVariant for T {
+    fn clone_variant(&self) -> Box<dyn ZVariant> {
+        Box::new(self.clone())
+    }
+
+    fn eq_variant(&self, other: &dyn ZVariant) -> bool {
+        other.as_any().downcast_ref::<T>().is_some_and(|other| self == other)
+    }
+}
+
+impl Clone for Box<dyn ZVariant> {
+    fn clone(&self) -> Self {
+        self.clone_variant()
+    }
+}
+
+impl PartialEq for Box<dyn ZVariant> {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_variant(other.as_ref())
+    }
+}
+
+// Derived `PartialEq`s of structs with a `Box<dyn ZVariant>` field need this one, see rust-lang/rust#31740.
+impl PartialEq<&Self> for Box<dyn ZVariant> {
+    fn eq(&self, other: &&Self) -> bool {
+        self.eq_variant(other.as_ref())
+    }
+}
+
+/// An empty `void` variant.
+impl Default for Box<dyn ZVariant> {
+    fn default() -> Self {
+        Box::new(Empty)
+    }
+}
+
+zvariant!(i8, "int8");
+zvariant!(i16, "int16");
+zvariant!(i32, "int32");
+zvariant!(i64, "int64");
+
+zvariant!(u8, "uint8");
+zvariant!(u16, "uint16");
+zvariant!(u32, "uint32");
+zvariant!(u64, "uint64");
+
+zvariant!(f32, "float32");
+
+zvariant!(f64, "float64");
+
+zvariant!(bool, "bool");
+
+zvariant!(char, "char");
+
+zvariant!(String, "String");
+
+
+#[derive(Default, PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename = "void")]
+pub struct Empty;
+
+#[typetag::serde(name = "void")]
+impl ZVariant for Empty {}
+
+/// A reference to a type, written as its name by ResourceLib, e.g. `TArray<SVector3>`.
+#[derive(Default, PartialEq, Eq, Hash, Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TypeId(pub String);
+
+impl TypeId {
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
\ No newline at end of file
@@ -0,0 +1,289 @@
+//! Conversions between the generated math classes and the types of glam and mint, with the `glam` and `mint`
+//! features.
+//!
+//! | Class                      | glam                 | mint                    |
+//! |----------------------------|----------------------|-------------------------|
+//! | `SVector2`                 | `Vec2`               | `Vector2<f32>`          |
+//! | `SVector3`, `SColorRGB`    | `Vec3`               | `Vector3<f32>`          |
+//! | `SVector4`, `SColorRGBA`   | `Vec4`               | `Vector4<f32>`          |
+//! | `SQuaternion`              | `Quat`               | `Quaternion<f32>`       |
+//! | `SMatrix43`                | `Affine3A`, `Mat4`   | `ColumnMatrix3x4<f32>`  |
+//!
+//! `SMatrix43` stores the axes and the translation of an affine transform as columns.
+
+#[cfg(feature = "glam")]
+use glam::Affine3A;
+
+/// Implements the conversions of a math class, invoked by glacier-codegen for every class named and shaped like one.
+/// Expands to nothing without the `glam` and `mint` features.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! math_conversions {
+    ($kind:ident, $ty:ident) => {
+        $crate::__glam_conversions!($kind, $ty);
+        $crate::__mint_conversions!($kind, $ty);
+    };
+}
+
+#[cfg(feature = "glam")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __glam_conversions {
+    (vector2, $ty:ident) => {
+        impl From<$ty> for $crate::glam::Vec2 {
+            fn from(value: $ty) -> Self {
+                $crate::glam::Vec2::new(value.x, value.y)
+            }
+        }
+
+        impl From<$crate::glam::Vec2> for $ty {
+            fn from(value: $crate::glam::Vec2) -> Self {
+                $ty { x: value.x, y: value.y }
+            }
+        }
+    };
+    (vector3, $ty:ident) => {
+        impl From<$ty> for $crate::glam::Vec3 {
+            fn from(value: $ty) -> Self {
+                $crate::glam::Vec3::new(value.x, value.y, value.z)
+            }
+        }
+
+        impl From<$crate::glam::Vec3> for $ty {
+            fn from(value: $crate::glam::Vec3) -> Self {
+                $ty { x: value.x, y: value.y, z: value.z }
+            }
+        }
+    };
+    (vector4, $ty:ident) => {
+        impl From<$ty> for $crate::glam::Vec4 {
+            fn from(value: $ty) -> Self {
+                $crate::glam::Vec4::new(value.x, value.y, value.z, value.w)
+            }
+        }
+
+        impl From<$crate::glam::Vec4> for $ty {
+            fn from(value: $crate::glam::Vec4) -> Self {
+                $ty { x: value.x, y: value.y, z: value.z, w: value.w }
+            }
+        }
+    };
+    (quaternion, $ty:ident) => {
+        impl From<$ty> for $crate::glam::Quat {
+            fn from(value: $ty) -> Self {
+                $crate::glam::Quat::from_xyzw(value.x, value.y, value.z, value.w)
+            }
+        }
+
+        impl From<$crate::glam::Quat> for $ty {
+            fn from(value: $crate::glam::Quat) -> Self {
+                $ty { x: value.x, y: value.y, z: value.z, w: value.w }
+            }
+        }
+    };
+    (color_rgb, $ty:ident) => {
+        impl From<$ty> for $crate::glam::Vec3 {
+            fn from(value: $ty) -> Self {
+                $crate::glam::Vec3::new(value.r, value.g, value.b)
+            }
+        }
+
+        impl From<$crate::glam::Vec3> for $ty {
+            fn from(value: $crate::glam::Vec3) -> Self {
+                $ty { r: value.x, g: value.y, b: value.z }
+            }
+        }
+    };
+    (color_rgba, $ty:ident) => {
+        impl From<$ty> for $crate::glam::Vec4 {
+            fn from(value: $ty) -> Self {
+                $crate::glam::Vec4::new(value.r, value.g, value.b, value.a)
+            }
+        }
+
+        impl From<$crate::glam::Vec4> for $ty {
+            fn from(value: $crate::glam::Vec4) -> Self {
+                $ty { r: value.x, g: value.y, b: value.z, a: value.w }
+            }
+        }
+    };
+    // The axes are `SVector3`s, converted by their own implementations.
+    (matrix43, $ty:ident) => {
+        impl From<$ty> for $crate::glam::Affine3A {
+            fn from(value: $ty) -> Self {
+                $crate::glam::Affine3A::from_cols(
+                    $crate::glam::Vec3::from(value.x_axis).into(),
+                    $crate::glam::Vec3::from(value.y_axis).into(),
+                    $crate::glam::Vec3::from(value.z_axis).into(),
+                    $crate::glam::Vec3::from(value.trans).into(),
+                )
+            }
+        }
+
+        impl From<$crate::glam::Affine3A> for $ty {
+            fn from(value: $crate::glam::Affine3A) -> Self {
+                $ty {
+                    x_axis: $crate::glam::Vec3::from(value.matrix3.x_axis).into(),
+                    y_axis: $crate::glam::Vec3::from(value.matrix3.y_axis).into(),
+                    z_axis: $crate::glam::Vec3::from(value.matrix3.z_axis).into(),
+                    trans: $crate::glam::Vec3::from(value.translation).into(),
+                }
+            }
+        }
+
+        impl From<$ty> for $crate::glam::Mat4 {
+            fn from(value: $ty) -> Self {
+                $crate::glam::Mat4::from($crate::glam::Affine3A::from(value))
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "glam"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __glam_conversions {
+    ($kind:ident, $ty:ident) => {};
+}
+
+#[cfg(feature = "mint")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __mint_conversions {
+    (vector2, $ty:ident) => {
+        impl From<$ty> for $crate::mint::Vector2<f32> {
+            fn from(value: $ty) -> Self {
+                $crate::mint::Vector2 { x: value.x, y: value.y }
+            }
+        }
+
+        impl From<$crate::mint::Vector2<f32>> for $ty {
+            fn from(value: $crate::mint::Vector2<f32>) -> Self {
+                $ty { x: value.x, y: value.y }
+            }
+        }
+    };
+    (vector3, $ty:ident) => {
+        impl From<$ty> for $crate::mint::Vector3<f32> {
+            fn from(value: $ty) -> Self {
+                $crate::mint::Vector3 { x: value.x, y: value.y, z: value.z }
+            }
+        }
+
+        impl From<$crate::mint::Vector3<f32>> for $ty {
+            fn from(value: $crate::mint::Vector3<f32>) -> Self {
+                $ty { x: value.x, y: value.y, z: value.z }
+            }
+        }
+    };
+    (vector4, $ty:ident) => {
+        impl From<$ty> for $crate::mint::Vector4<f32> {
+            fn from(value: $ty) -> Self {
+                $crate::mint::Vector4 { x: value.x, y: value.y, z: value.z, w: value.w }
+            }
+        }
+
+        impl From<$crate::mint::Vector4<f32>> for $ty {
+            fn from(value: $crate::mint::Vector4<f32>) -> Self {
+                $ty { x: value.x, y: value.y, z: value.z, w: value.w }
+            }
+        }
+    };
+    (quaternion, $ty:ident) => {
+        impl From<$ty> for $crate::mint::Quaternion<f32> {
+            fn from(value: $ty) -> Self {
+                $crate::mint::Quaternion { v: $crate::mint::Vector3 { x: value.x, y: value.y, z: value.z }, s: value.w }
+            }
+        }
+
+        impl From<$crate::mint::Quaternion<f32>> for $ty {
+            fn from(value: $crate::mint::Quaternion<f32>) -> Self {
+                $ty { x: value.v.x, y: value.v.y, z: value.v.z, w: value.s }
+            }
+        }
+    };
+    (color_rgb, $ty:ident) => {
+        impl From<$ty> for $crate::mint::Vector3<f32> {
+            fn from(value: $ty) -> Self {
+                $crate::mint::Vector3 { x: value.r, y: value.g, z: value.b }
+            }
+        }
+
+        impl From<$crate::mint::Vector3<f32>> for $ty {
+            fn from(value: $crate::mint::Vector3<f32>) -> Self {
+                $ty { r: value.x, g: value.y, b: value.z }
+            }
+        }
+    };
+    (color_rgba, $ty:ident) => {
+        impl From<$ty> for $crate::mint::Vector4<f32> {
+            fn from(value: $ty) -> Self {
+                $crate::mint::Vector4 { x: value.r, y: value.g, z: value.b, w: value.a }
+            }
+        }
+
+        impl From<$crate::mint::Vector4<f32>> for $ty {
+            fn from(value: $crate::mint::Vector4<f32>) -> Self {
+                $ty { r: value.x, g: value.y, b: value.z, a: value.w }
+            }
+        }
+    };
+    (matrix43, $ty:ident) => {
+        impl From<$ty> for $crate::mint::ColumnMatrix3x4<f32> {
+            fn from(value: $ty) -> Self {
+                $crate::mint::ColumnMatrix3x4 {
+                    x: value.x_axis.into(),
+                    y: value.y_axis.into(),
+                    z: value.z_axis.into(),
+                    w: value.trans.into(),
+                }
+            }
+        }
+
+        impl From<$crate::mint::ColumnMatrix3x4<f32>> for $ty {
+            fn from(value: $crate::mint::ColumnMatrix3x4<f32>) -> Self {
+                $ty { x_axis: value.x.into(), y_axis: value.y.into(), z_axis: value.z.into(), trans: value.w.into() }
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "mint"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __mint_conversions {
+    ($kind:ident, $ty:ident) => {};
+}
+
+/// Composes the local transforms of a hierarchy into world transforms. `parents[i]` is the index of the parent of
+/// the `i`th transform, whose local transform is relative to it.
+///
+/// Transforms with a parent out of range, or that are their own ancestor, are treated as roots.
+#[cfg(feature = "glam")]
+pub fn world_transforms(locals: &[Affine3A], parents: &[Option<usize>]) -> Vec<Affine3A> {
+    let mut world = vec![None; locals.len()];
+    for index in 0..locals.len() {
+        // Walk up to the first ancestor with a known world transform, then compose back down.
+        let mut chain = vec![index];
+        let mut current = index;
+        while world[current].is_none() {
+            match parents.get(current).copied().flatten().filter(|&parent| parent < locals.len()) {
+                Some(parent) if !chain.contains(&parent) => {
+                    chain.push(parent);
+                    current = parent;
+                }
+                _ => break,
+            }
+        }
+        let mut transform = world[current].unwrap_or(Affine3A::IDENTITY);
+        for &link in chain.iter().rev() {
+            if world[link].is_none() {
+                transform *= locals[link];
+                world[link] = Some(transform);
+            } else {
+                transform = world[link].unwrap();
+            }
+        }
+    }
+    world.into_iter().map(|transform| transform.unwrap_or(Affine3A::IDENTITY)).collect()
+}
@@ -0,0 +1,71 @@
+//! Access to the fields of the generated classes by the names ZHMGen.h uses for them.
+
+use std::any::Any;
+use serde_json::Value;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ReflectError {
+    #[error("{type_name} has no field {field}")]
+    UnknownField { type_name: &'static str, field: String },
+
+    #[error("Invalid value for {type_name}::{field}: {source}")]
+    InvalidValue { type_name: &'static str, field: &'static str, source: serde_json::Error },
+}
+
+/// The kind of a generated type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeKind {
+    Class,
+    Enum,
+}
+
+/// A generated class or enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TypeInfo {
+    /// The name in ZHMGen.h, e.g. `SEntityTemplateProperty`.
+    pub name: &'static str,
+    pub kind: TypeKind,
+    /// The size of a class in the game, in bytes.
+    pub size: Option<usize>,
+    /// The alignment of a class in the game, in bytes.
+    pub alignment: Option<usize>,
+    /// The fields of a class, in declaration order.
+    pub fields: &'static [FieldInfo],
+    /// The names of the variants of an enum in ZHMGen.h, without the aliases.
+    pub variants: &'static [&'static str],
+}
+
+impl TypeInfo {
+    pub fn field(&self, name: &str) -> Option<&'static FieldInfo> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// A field of a generated class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldInfo {
+    /// The name in ZHMGen.h, e.g. `nPropertyID`.
+    pub name: &'static str,
+    /// The name of the Rust field, e.g. `n_property_id`.
+    pub rust_name: &'static str,
+    /// The C++ type, e.g. `TArray<SEntityTemplateProperty>`.
+    pub type_name: &'static str,
+    /// The offset of the field in bytes.
+    pub offset: Option<usize>,
+}
+
+/// Implemented by every generated class and enum. Fields are named like in ZHMGen.h and their values are read and
+/// written in the JSON format of ResourceLib.
+pub trait Reflect {
+    fn type_info(&self) -> &'static TypeInfo;
+
+    /// The field as `&dyn Any`, to downcast to its Rust type.
+    fn field(&self, name: &str) -> Option<&dyn Any>;
+
+    fn field_mut(&mut self, name: &str) -> Option<&mut dyn Any>;
+
+    fn get_field(&self, name: &str) -> Result<Value, ReflectError>;
+
+    fn set_field(&mut self, name: &str, value: Value) -> Result<(), ReflectError>;
+}
@@ -11,7 +11,9 @@ macro_rules! warn {
 }
 
 fn main() {
-    let lib_path = PathBuf::from(env::var("DEP_RESOURCELIB_SYS_LIB_PATH").unwrap());
-    println!("cargo:lib_path={}", lib_path.display());
+    // Without the `native` feature, resourcelib-sys isn't built.
+    if let Ok(lib_path) = env::var("DEP_RESOURCELIB_SYS_LIB_PATH") {
+        println!("cargo:lib_path={}", PathBuf::from(lib_path).display());
+    }
     // warn!("{:?}", lib_path);
 }
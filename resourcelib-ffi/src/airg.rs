@@ -0,0 +1,403 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+#[cfg(feature = "hm2016")]
+use glacier_hm2016 as hm2016_bindings;
+#[cfg(feature = "hm2")]
+use glacier_hm2 as hm2_bindings;
+#[cfg(feature = "hm3")]
+use glacier_hm3 as hm3_bindings;
+use crate::ResourceLibError;
+
+pub mod export;
+
+/// Marks an unused neighbor slot of a waypoint.
+pub const NO_NEIGHBOR: u16 = u16::MAX;
+
+/// The number of neighbor slots of a waypoint, one per compass direction.
+pub const NEIGHBOR_COUNT: usize = 8;
+
+/// The most waypoints a grid can hold, as neighbors are `uint16` indices and [`NO_NEIGHBOR`] is reserved.
+pub const MAX_WAYPOINTS: usize = NO_NEIGHBOR as usize;
+
+/// Returns the neighbor slot pointing back from a neighbor, e.g. south for north.
+pub fn opposite_direction(direction: usize) -> usize {
+    (direction + NEIGHBOR_COUNT / 2) % NEIGHBOR_COUNT
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Waypoint {
+    pub position: [f32; 4],
+    pub neighbors: [Option<usize>; NEIGHBOR_COUNT],
+    pub layer_index: i32,
+    pub dead_end: bool,
+    /// The bit of this waypoint in `m_LowVisibilityBits`.
+    pub low_visibility: bool,
+    /// The bit of this waypoint in `m_HighVisibilityBits`.
+    pub high_visibility: bool,
+    /// The block of `m_pVisibilityData` owned by this waypoint.
+    pub visibility_data: Vec<u8>,
+}
+
+impl Waypoint {
+    pub fn new(position: [f32; 3], layer_index: i32) -> Self {
+        Waypoint {
+            position: [position[0], position[1], position[2], 1.0],
+            neighbors: [None; NEIGHBOR_COUNT],
+            layer_index,
+            dead_end: false,
+            low_visibility: false,
+            high_visibility: false,
+            visibility_data: Vec::new(),
+        }
+    }
+
+    pub fn distance_to(&self, position: [f32; 3]) -> f32 {
+        (0..3).map(|i| (self.position[i] - position[i]).powi(2)).sum::<f32>().sqrt()
+    }
+
+    pub fn neighbor_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.neighbors.iter().flatten().copied()
+    }
+}
+
+/// A navigation graph built from the waypoints of a reasoning grid (AIRG).
+///
+/// Edits made to the graph are written back with [`ReasoningGridData::apply_graph`], which rebuilds the waypoint list,
+/// visibility data offsets, visibility and dead end bits and the bounds so the grid stays consistent for the generator.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReasoningGraph {
+    pub waypoints: Vec<Waypoint>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Frontier {
+    estimate: f32,
+    cost: f32,
+    waypoint: usize,
+}
+
+impl Eq for Frontier {}
+
+impl Ord for Frontier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate).then_with(|| self.waypoint.cmp(&other.waypoint))
+    }
+}
+
+impl PartialOrd for Frontier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl ReasoningGraph {
+    pub fn len(&self) -> usize {
+        self.waypoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.waypoints.is_empty()
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), ResourceLibError> {
+        if index < self.waypoints.len() {
+            Ok(())
+        } else {
+            Err(ResourceLibError::InvalidWaypoint(index))
+        }
+    }
+
+    fn distance(&self, from: usize, to: usize) -> f32 {
+        let target = &self.waypoints[to].position;
+        self.waypoints[from].distance_to([target[0], target[1], target[2]])
+    }
+
+    /// Finds the waypoint closest to a world position.
+    pub fn nearest_waypoint(&self, position: [f32; 3]) -> Option<usize> {
+        self.waypoints.iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.distance_to(position).total_cmp(&b.distance_to(position)))
+            .map(|(index, _)| index)
+    }
+
+    /// Finds the shortest path between two waypoints with A*, using the straight line distance as heuristic.
+    ///
+    /// The returned path includes both the start and the goal waypoint.
+    pub fn find_path(&self, start: usize, goal: usize) -> Result<Option<Vec<usize>>, ResourceLibError> {
+        self.check_index(start)?;
+        self.check_index(goal)?;
+
+        let mut costs = vec![f32::INFINITY; self.waypoints.len()];
+        let mut previous = vec![None; self.waypoints.len()];
+        let mut open = BinaryHeap::new();
+
+        costs[start] = 0.0;
+        open.push(Frontier { estimate: self.distance(start, goal), cost: 0.0, waypoint: start });
+
+        while let Some(Frontier { waypoint, cost, .. }) = open.pop() {
+            if waypoint == goal {
+                let mut path = vec![goal];
+                while let Some(prev) = previous[*path.last().unwrap()] {
+                    path.push(prev);
+                }
+                path.reverse();
+                return Ok(Some(path));
+            }
+            if cost > costs[waypoint] {
+                continue;
+            }
+
+            for neighbor in self.waypoints[waypoint].neighbor_indices() {
+                let neighbor_cost = cost + self.distance(waypoint, neighbor);
+                if neighbor_cost < costs[neighbor] {
+                    costs[neighbor] = neighbor_cost;
+                    previous[neighbor] = Some(waypoint);
+                    open.push(Frontier { estimate: neighbor_cost + self.distance(neighbor, goal), cost: neighbor_cost, waypoint: neighbor });
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Groups the waypoints into sets that are reachable from each other, ignoring link direction.
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut undirected = vec![Vec::new(); self.waypoints.len()];
+        for (index, waypoint) in self.waypoints.iter().enumerate() {
+            for neighbor in waypoint.neighbor_indices() {
+                undirected[index].push(neighbor);
+                undirected[neighbor].push(index);
+            }
+        }
+
+        let mut component_of = vec![None; self.waypoints.len()];
+        let mut components = Vec::new();
+        for start in 0..self.waypoints.len() {
+            if component_of[start].is_some() {
+                continue;
+            }
+            let mut component = Vec::new();
+            let mut queue = VecDeque::from([start]);
+            component_of[start] = Some(components.len());
+            while let Some(waypoint) = queue.pop_front() {
+                component.push(waypoint);
+                for &neighbor in &undirected[waypoint] {
+                    if component_of[neighbor].is_none() {
+                        component_of[neighbor] = Some(components.len());
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
+    /// Returns the waypoints that cannot be reached from any other waypoint and do not link anywhere.
+    pub fn isolated_waypoints(&self) -> Vec<usize> {
+        self.connected_components().into_iter()
+            .filter(|component| component.len() == 1)
+            .map(|component| component[0])
+            .collect()
+    }
+
+    pub fn add_waypoint(&mut self, waypoint: Waypoint) -> Result<usize, ResourceLibError> {
+        if self.waypoints.len() >= MAX_WAYPOINTS {
+            return Err(ResourceLibError::TooManyWaypoints(MAX_WAYPOINTS));
+        }
+        for neighbor in waypoint.neighbor_indices() {
+            self.check_index(neighbor)?;
+        }
+        self.waypoints.push(waypoint);
+        Ok(self.waypoints.len() - 1)
+    }
+
+    /// Links two waypoints in both directions, `direction` being the neighbor slot of `from` that points to `to`.
+    pub fn link(&mut self, from: usize, to: usize, direction: usize) -> Result<(), ResourceLibError> {
+        self.check_index(from)?;
+        self.check_index(to)?;
+        if direction >= NEIGHBOR_COUNT {
+            return Err(ResourceLibError::InvalidNeighborDirection(direction));
+        }
+        self.waypoints[from].neighbors[direction] = Some(to);
+        self.waypoints[to].neighbors[opposite_direction(direction)] = Some(from);
+        Ok(())
+    }
+
+    /// Removes every link between two waypoints.
+    pub fn unlink(&mut self, a: usize, b: usize) -> Result<(), ResourceLibError> {
+        self.check_index(a)?;
+        self.check_index(b)?;
+        for (from, to) in [(a, b), (b, a)] {
+            for neighbor in self.waypoints[from].neighbors.iter_mut() {
+                if *neighbor == Some(to) {
+                    *neighbor = None;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes a waypoint and its links. Waypoints after it shift down by one index.
+    pub fn remove_waypoint(&mut self, index: usize) -> Result<Waypoint, ResourceLibError> {
+        self.check_index(index)?;
+        let removed = self.waypoints.remove(index);
+        for waypoint in &mut self.waypoints {
+            for neighbor in waypoint.neighbors.iter_mut() {
+                *neighbor = match *neighbor {
+                    Some(n) if n == index => None,
+                    Some(n) if n > index => Some(n - 1),
+                    other => other,
+                };
+            }
+        }
+        Ok(removed)
+    }
+
+    /// The axis aligned bounds of all waypoints, as `(min, max)`.
+    pub fn bounds(&self) -> Option<([f32; 3], [f32; 3])> {
+        let first = self.waypoints.first()?.position;
+        let mut min = [first[0], first[1], first[2]];
+        let mut max = min;
+        for waypoint in &self.waypoints {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(waypoint.position[axis]);
+                max[axis] = max[axis].max(waypoint.position[axis]);
+            }
+        }
+        Some((min, max))
+    }
+}
+
+fn get_bit(bytes: &[u8], index: usize) -> bool {
+    bytes.get(index / 8).is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+}
+
+fn pack_bits(bits: impl Iterator<Item = bool>) -> (Vec<u8>, u32) {
+    let mut bytes = Vec::new();
+    let mut size = 0;
+    for (index, bit) in bits.enumerate() {
+        if index % 8 == 0 {
+            bytes.push(0);
+        }
+        if bit {
+            bytes[index / 8] |= 1 << (index % 8);
+        }
+        size += 1;
+    }
+    (bytes, size)
+}
+
+/// Splits the shared visibility data into one block per waypoint, using the offsets of all waypoints as boundaries.
+fn split_visibility_data(data: &[u8], offsets: &[u32]) -> Vec<Vec<u8>> {
+    let mut boundaries = offsets.iter().map(|&offset| offset as usize).filter(|&offset| offset <= data.len()).collect::<Vec<_>>();
+    boundaries.push(data.len());
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    offsets.iter()
+        .map(|&offset| {
+            let start = offset as usize;
+            match boundaries.iter().find(|&&boundary| boundary > start) {
+                Some(&end) => data[start..end].to_vec(),
+                None => Vec::new(),
+            }
+        })
+        .collect()
+}
+
+pub trait ReasoningGridData {
+    /// Builds a navigation graph from the waypoints of the grid.
+    fn to_graph(&self) -> ReasoningGraph;
+
+    /// Replaces the waypoints of the grid with those of the graph, regenerating node count, visibility data offsets,
+    /// the low visibility, high visibility and dead end bits and the bounds of the grid. The bounds are left as they
+    /// are for an empty graph, and the width and spacing of the grid are kept.
+    fn apply_graph(&mut self, graph: &ReasoningGraph) -> Result<(), ResourceLibError>;
+}
+
+macro_rules! impl_reasoning_grid {
+    ($bindings:ident) => {
+        impl ReasoningGridData for $bindings::properties::SReasoningGrid {
+            fn to_graph(&self) -> ReasoningGraph {
+                let offsets = self.waypoint_list.iter().map(|waypoint| waypoint.n_vision_data_offset).collect::<Vec<_>>();
+                let visibility_data = split_visibility_data(&self.p_visibility_data, &offsets);
+
+                let waypoints = self.waypoint_list.iter()
+                    .zip(visibility_data)
+                    .enumerate()
+                    .map(|(index, (waypoint, visibility_data))| Waypoint {
+                        position: [waypoint.v_pos.x, waypoint.v_pos.y, waypoint.v_pos.z, waypoint.v_pos.w],
+                        neighbors: waypoint.neighbors.map(|neighbor| {
+                            (neighbor != NO_NEIGHBOR && (neighbor as usize) < self.waypoint_list.len()).then_some(neighbor as usize)
+                        }),
+                        layer_index: waypoint.n_layer_index,
+                        dead_end: get_bit(&self.dead_end_data.a_bytes, index),
+                        low_visibility: get_bit(&self.low_visibility_bits.a_bytes, index),
+                        high_visibility: get_bit(&self.high_visibility_bits.a_bytes, index),
+                        visibility_data,
+                    })
+                    .collect();
+
+                ReasoningGraph { waypoints }
+            }
+
+            fn apply_graph(&mut self, graph: &ReasoningGraph) -> Result<(), ResourceLibError> {
+                use $bindings::properties::{SVector4, SgWaypoint};
+
+                if graph.waypoints.len() > MAX_WAYPOINTS {
+                    return Err(ResourceLibError::TooManyWaypoints(MAX_WAYPOINTS));
+                }
+
+                let mut visibility_data = Vec::new();
+                let mut waypoint_list = Vec::with_capacity(graph.waypoints.len());
+                for waypoint in &graph.waypoints {
+                    let mut neighbors = [NO_NEIGHBOR; NEIGHBOR_COUNT];
+                    for (slot, neighbor) in neighbors.iter_mut().zip(waypoint.neighbors) {
+                        if let Some(neighbor) = neighbor {
+                            graph.check_index(neighbor)?;
+                            *slot = neighbor as u16;
+                        }
+                    }
+
+                    waypoint_list.push(SgWaypoint {
+                        neighbors,
+                        n_vision_data_offset: visibility_data.len() as u32,
+                        n_layer_index: waypoint.layer_index,
+                        v_pos: SVector4 {
+                            x: waypoint.position[0],
+                            y: waypoint.position[1],
+                            z: waypoint.position[2],
+                            w: waypoint.position[3],
+                        },
+                    });
+                    visibility_data.extend_from_slice(&waypoint.visibility_data);
+                }
+
+                let bits = |bit: fn(&Waypoint) -> bool| pack_bits(graph.waypoints.iter().map(bit));
+                (self.dead_end_data.a_bytes, self.dead_end_data.n_size) = bits(|waypoint| waypoint.dead_end);
+                (self.low_visibility_bits.a_bytes, self.low_visibility_bits.n_size) = bits(|waypoint| waypoint.low_visibility);
+                (self.high_visibility_bits.a_bytes, self.high_visibility_bits.n_size) = bits(|waypoint| waypoint.high_visibility);
+
+                if let Some((min, max)) = graph.bounds() {
+                    let properties = &mut self.properties;
+                    (properties.v_min.x, properties.v_min.y, properties.v_min.z) = (min[0], min[1], min[2]);
+                    (properties.v_max.x, properties.v_max.y, properties.v_max.z) = (max[0], max[1], max[2]);
+                }
+
+                self.waypoint_list = waypoint_list;
+                self.p_visibility_data = visibility_data;
+                self.n_node_count = graph.waypoints.len() as u32;
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "hm2016")]
+impl_reasoning_grid!(hm2016_bindings);
+#[cfg(feature = "hm2")]
+impl_reasoning_grid!(hm2_bindings);
+#[cfg(feature = "hm3")]
+impl_reasoning_grid!(hm3_bindings);
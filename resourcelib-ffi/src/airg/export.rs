@@ -0,0 +1,234 @@
+use std::io::Write;
+use serde_json::json;
+use crate::airg::ReasoningGraph;
+use crate::ResourceLibError;
+
+/// Returns every link of the graph once, regardless of the direction it was stored in.
+fn unique_edges(graph: &ReasoningGraph) -> Vec<(usize, usize)> {
+    let mut edges = graph.waypoints.iter()
+        .enumerate()
+        .flat_map(|(index, waypoint)| waypoint.neighbor_indices().map(move |neighbor| (index.min(neighbor), index.max(neighbor))))
+        .filter(|(a, b)| a != b)
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+    edges.dedup();
+    edges
+}
+
+/// Writes the waypoint graph as Wavefront OBJ line geometry, one vertex per waypoint.
+pub fn write_obj<W: Write>(graph: &ReasoningGraph, writer: &mut W) -> Result<(), ResourceLibError> {
+    writeln!(writer, "o ReasoningGrid")?;
+    for waypoint in &graph.waypoints {
+        writeln!(writer, "v {} {} {}", waypoint.position[0], waypoint.position[1], waypoint.position[2])?;
+    }
+    for (a, b) in unique_edges(graph) {
+        writeln!(writer, "l {} {}", a + 1, b + 1)?;
+    }
+    Ok(())
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let triple = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(triple >> (18 - i * 6)) as usize & 0x3F] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Writes the waypoint graph as a self-contained glTF 2.0 document with a single line mesh.
+///
+/// Positions are converted from the game's Z-up space to glTF's Y-up space. glTF doesn't allow empty buffers or
+/// accessors, so a graph without links is written as points and one without waypoints as a node without a mesh.
+pub fn write_gltf<W: Write>(graph: &ReasoningGraph, writer: &mut W) -> Result<(), ResourceLibError> {
+    let edges = unique_edges(graph);
+    let positions = graph.waypoints.iter()
+        .map(|waypoint| [waypoint.position[0], waypoint.position[2], -waypoint.position[1]])
+        .collect::<Vec<_>>();
+
+    let mut node = json!({ "name": "ReasoningGrid" });
+    let mut document = json!({
+        "asset": { "version": "2.0", "generator": "resourcelib-ffi" },
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+    });
+
+    if !positions.is_empty() {
+        let mut buffer = Vec::with_capacity(positions.len() * 12 + edges.len() * 8);
+        for position in &positions {
+            for component in position {
+                buffer.extend_from_slice(&component.to_le_bytes());
+            }
+        }
+        let positions_length = buffer.len();
+        for (a, b) in &edges {
+            buffer.extend_from_slice(&(*a as u32).to_le_bytes());
+            buffer.extend_from_slice(&(*b as u32).to_le_bytes());
+        }
+
+        let mut min = [f32::MAX; 3];
+        let mut max = [f32::MIN; 3];
+        for position in &positions {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(position[axis]);
+                max[axis] = max[axis].max(position[axis]);
+            }
+        }
+
+        let mut primitive = json!({ "attributes": { "POSITION": 0 }, "mode": 0 });
+        let mut buffer_views = vec![json!({ "buffer": 0, "byteOffset": 0, "byteLength": positions_length, "target": 34962 })];
+        let mut accessors = vec![
+            json!({ "bufferView": 0, "componentType": 5126, "count": positions.len(), "type": "VEC3", "min": min, "max": max }),
+        ];
+        if !edges.is_empty() {
+            primitive["indices"] = json!(1);
+            primitive["mode"] = json!(1);
+            let indices_length = buffer.len() - positions_length;
+            buffer_views.push(json!({ "buffer": 0, "byteOffset": positions_length, "byteLength": indices_length, "target": 34963 }));
+            accessors.push(json!({ "bufferView": 1, "componentType": 5125, "count": edges.len() * 2, "type": "SCALAR" }));
+        }
+
+        node["mesh"] = json!(0);
+        document["meshes"] = json!([{ "primitives": [primitive] }]);
+        document["buffers"] = json!([{
+            "byteLength": buffer.len(),
+            "uri": format!("data:application/octet-stream;base64,{}", base64(&buffer)),
+        }]);
+        document["bufferViews"] = json!(buffer_views);
+        document["accessors"] = json!(accessors);
+    }
+    document["nodes"] = json!([node]);
+
+    serde_json::to_writer(writer, &document)?;
+    Ok(())
+}
+
+/// A top-down grid of values between 0 and 1, with `None` for cells without waypoints.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heatmap {
+    pub width: usize,
+    pub height: usize,
+    /// The world position of the top left corner of the first cell.
+    pub origin: [f32; 2],
+    pub cell_size: f32,
+    pub values: Vec<Option<f32>>,
+}
+
+/// Builds a heatmap of how much visibility data every waypoint carries, counted in set bits.
+///
+/// Waypoints stacked on different layers share a cell, in which case the highest value is kept.
+pub fn visibility_heatmap(graph: &ReasoningGraph, cell_size: f32) -> Result<Heatmap, ResourceLibError> {
+    if cell_size <= 0.0 {
+        return Err(ResourceLibError::InvalidCellSize(cell_size));
+    }
+    let Some((min, max)) = graph.bounds() else {
+        return Ok(Heatmap { width: 0, height: 0, origin: [0.0; 2], cell_size, values: Vec::new() });
+    };
+
+    let width = ((max[0] - min[0]) / cell_size).floor() as usize + 1;
+    let height = ((max[1] - min[1]) / cell_size).floor() as usize + 1;
+    let mut values = vec![None; width * height];
+
+    let visibility = graph.waypoints.iter()
+        .map(|waypoint| waypoint.visibility_data.iter().map(|byte| byte.count_ones()).sum::<u32>())
+        .collect::<Vec<_>>();
+    let highest = visibility.iter().copied().max().unwrap_or(0).max(1) as f32;
+
+    for (waypoint, visible) in graph.waypoints.iter().zip(visibility) {
+        let column = ((waypoint.position[0] - min[0]) / cell_size) as usize;
+        // Rows run from the top (highest Y) down, as seen on a map.
+        let row = ((max[1] - waypoint.position[1]) / cell_size) as usize;
+        let cell = &mut values[row.min(height - 1) * width + column.min(width - 1)];
+        let value = visible as f32 / highest;
+        *cell = Some(cell.map_or(value, |existing: f32| existing.max(value)));
+    }
+
+    Ok(Heatmap { width, height, origin: [min[0], max[1]], cell_size, values })
+}
+
+/// Maps a value between 0 and 1 onto a blue, green, yellow, red color ramp.
+pub fn heat_color(value: f32) -> [u8; 3] {
+    let value = value.clamp(0.0, 1.0) * 3.0;
+    let ramp = |t: f32| (t.clamp(0.0, 1.0) * 255.0).round() as u8;
+    match value {
+        v if v < 1.0 => [0, ramp(v), ramp(1.0 - v)],
+        v if v < 2.0 => [ramp(v - 1.0), 255, 0],
+        v => [255, ramp(3.0 - v), 0],
+    }
+}
+
+impl Heatmap {
+    pub fn get(&self, column: usize, row: usize) -> Option<f32> {
+        if column >= self.width || row >= self.height {
+            return None;
+        }
+        self.values[row * self.width + column]
+    }
+
+    /// Converts the heatmap to RGBA pixels, leaving empty cells transparent.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        self.values.iter()
+            .flat_map(|value| match value {
+                Some(value) => {
+                    let [r, g, b] = heat_color(*value);
+                    [r, g, b, 255]
+                }
+                None => [0, 0, 0, 0],
+            })
+            .collect()
+    }
+
+    /// Writes the heatmap as an SVG document with one square per occupied cell, in world units.
+    pub fn write_svg<W: Write>(&self, writer: &mut W) -> Result<(), ResourceLibError> {
+        let width = self.width as f32 * self.cell_size;
+        let height = self.height as f32 * self.cell_size;
+        writeln!(
+            writer,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="{}" height="{}">"#,
+            self.origin[0], -self.origin[1], width, height, self.width, self.height
+        )?;
+        for row in 0..self.height {
+            for column in 0..self.width {
+                let Some(value) = self.get(column, row) else {
+                    continue;
+                };
+                let [r, g, b] = heat_color(value);
+                writeln!(
+                    writer,
+                    r##"  <rect x="{}" y="{}" width="{}" height="{}" fill="#{:02x}{:02x}{:02x}"><title>{:.3}</title></rect>"##,
+                    self.origin[0] + column as f32 * self.cell_size,
+                    -self.origin[1] + row as f32 * self.cell_size,
+                    self.cell_size,
+                    self.cell_size,
+                    r, g, b,
+                    value
+                )?;
+            }
+        }
+        writeln!(writer, "</svg>")?;
+        Ok(())
+    }
+
+    /// Writes the heatmap as an RGBA PNG image with one pixel per cell. An empty heatmap, e.g. of a graph without
+    /// waypoints, can't be written as a PNG.
+    #[cfg(feature = "png")]
+    pub fn write_png<W: Write>(&self, writer: W) -> Result<(), ResourceLibError> {
+        if self.values.is_empty() {
+            return Err(ResourceLibError::EmptyHeatmap);
+        }
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()
+            .and_then(|mut png_writer| png_writer.write_image_data(&self.to_rgba()))
+            .map_err(|e| ResourceLibError::IoError(std::io::Error::other(e)))
+    }
+}
@@ -0,0 +1,165 @@
+use std::io::Write;
+#[cfg(feature = "hm2016")]
+use glacier_hm2016 as hm2016_bindings;
+#[cfg(feature = "hm2")]
+use glacier_hm2 as hm2_bindings;
+#[cfg(feature = "hm3")]
+use glacier_hm3 as hm3_bindings;
+use crate::ResourceLibError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationEvent {
+    pub name: String,
+    /// The start of the event in seconds, relative to the start of the take.
+    pub time: f32,
+    pub duration: f32,
+}
+
+impl AnimationEvent {
+    pub fn end(&self) -> f32 {
+        self.time + self.duration
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationTrack {
+    pub name: String,
+    pub events: Vec<AnimationEvent>,
+}
+
+/// A version independent copy of an animation take (ATMD).
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationTake {
+    pub name: String,
+    /// The length of the take in seconds.
+    pub duration: f32,
+    pub tracks: Vec<AnimationTrack>,
+    /// The `(m_IDHigh, m_IDLow)` pairs of the animations used by the take.
+    pub animations: Vec<(u32, u32)>,
+}
+
+impl AnimationTake {
+    pub fn track(&self, name: &str) -> Option<&AnimationTrack> {
+        self.tracks.iter().find(|track| track.name == name)
+    }
+
+    pub fn event_count(&self) -> usize {
+        self.tracks.iter().map(|track| track.events.len()).sum()
+    }
+
+    /// Iterates over all events of all tracks as `(track index, event index, event)`.
+    pub fn events(&self) -> impl Iterator<Item = (usize, usize, &AnimationEvent)> {
+        self.tracks.iter().enumerate().flat_map(|(track_index, track)| {
+            track.events.iter().enumerate().map(move |(event_index, event)| (track_index, event_index, event))
+        })
+    }
+
+    /// Moves and resizes an event. The event has to stay within the duration of the take.
+    pub fn set_event_timing(&mut self, track: usize, event: usize, time: f32, duration: f32) -> Result<(), ResourceLibError> {
+        let take_duration = self.duration;
+        let target = self.tracks.get_mut(track)
+            .and_then(|found| found.events.get_mut(event))
+            .ok_or(ResourceLibError::InvalidAnimationEvent(track, event))?;
+        if !(time >= 0.0 && duration >= 0.0 && time + duration <= take_duration) {
+            return Err(ResourceLibError::InvalidAnimationEvent(track, event));
+        }
+        target.time = time;
+        target.duration = duration;
+        Ok(())
+    }
+
+    /// Shifts every event of a track by `offset` seconds, failing without changes if an event would leave the take.
+    pub fn shift_track(&mut self, track: usize, offset: f32) -> Result<(), ResourceLibError> {
+        let take_duration = self.duration;
+        let found = self.tracks.get_mut(track).ok_or(ResourceLibError::InvalidAnimationEvent(track, 0))?;
+        if let Some(event) = found.events.iter().position(|event| event.time + offset < 0.0 || event.end() + offset > take_duration) {
+            return Err(ResourceLibError::InvalidAnimationEvent(track, event));
+        }
+        for event in &mut found.events {
+            event.time += offset;
+        }
+        Ok(())
+    }
+
+    /// Changes the duration of the take, scaling all event timings along with it.
+    pub fn rescale(&mut self, duration: f32) {
+        let factor = if self.duration > 0.0 { duration / self.duration } else { 1.0 };
+        for event in self.tracks.iter_mut().flat_map(|track| track.events.iter_mut()) {
+            event.time *= factor;
+            event.duration *= factor;
+        }
+        self.duration = duration;
+    }
+
+    /// Writes a human readable timeline of the take, listing the events of every track in chronological order.
+    pub fn write_timeline<W: Write>(&self, writer: &mut W) -> Result<(), ResourceLibError> {
+        writeln!(writer, "Take \"{}\": {:.3}s, {} tracks, {} events", self.name, self.duration, self.tracks.len(), self.event_count())?;
+        writeln!(writer, "Animations:")?;
+        for (index, (id_high, id_low)) in self.animations.iter().enumerate() {
+            writeln!(writer, "  [{}] {:08X}{:08X}", index, id_high, id_low)?;
+        }
+        for track in &self.tracks {
+            writeln!(writer, "Track \"{}\":", track.name)?;
+            let mut events = track.events.iter().collect::<Vec<_>>();
+            events.sort_by(|a, b| a.time.total_cmp(&b.time));
+            for event in events {
+                writeln!(writer, "  {:>8.3}s - {:>8.3}s  {}", event.time, event.end(), event.name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub trait AnimationTakeData {
+    fn to_animation_take(&self) -> AnimationTake;
+
+    /// Writes the take back, replacing the tracks, events and animations of the resource.
+    fn apply_animation_take(&mut self, take: &AnimationTake);
+}
+
+macro_rules! impl_animation_take {
+    ($bindings:ident) => {
+        impl AnimationTakeData for $bindings::properties::ZamdTake {
+            fn to_animation_take(&self) -> AnimationTake {
+                AnimationTake {
+                    name: self.s_name.to_string(),
+                    duration: self.f_duration,
+                    tracks: self.a_tracks.iter()
+                        .map(|track| AnimationTrack {
+                            name: track.s_name.to_string(),
+                            events: track.a_events.iter()
+                                .map(|event| AnimationEvent { name: event.s_name.to_string(), time: event.f_time, duration: event.f_duration })
+                                .collect(),
+                        })
+                        .collect(),
+                    animations: self.a_animations.iter().map(|animation| (animation.id_high, animation.id_low)).collect(),
+                }
+            }
+
+            fn apply_animation_take(&mut self, take: &AnimationTake) {
+                use $bindings::properties::{ZRuntimeResourceId, ZamdEvent, ZamdTrack};
+
+                self.s_name = take.name.as_str().into();
+                self.f_duration = take.duration;
+                self.a_tracks = take.tracks.iter()
+                    .map(|track| ZamdTrack {
+                        s_name: track.name.as_str().into(),
+                        a_events: track.events.iter()
+                            .map(|event| ZamdEvent { s_name: event.name.as_str().into(), f_time: event.time, f_duration: event.duration })
+                            .collect(),
+                    })
+                    .collect();
+                self.a_animations = take.animations.iter()
+                    .map(|&(id_high, id_low)| ZRuntimeResourceId { id_high, id_low })
+                    .collect();
+            }
+        }
+    };
+}
+
+#[cfg(feature = "hm2016")]
+impl_animation_take!(hm2016_bindings);
+#[cfg(feature = "hm2")]
+impl_animation_take!(hm2_bindings);
+#[cfg(feature = "hm3")]
+impl_animation_take!(hm3_bindings);
@@ -0,0 +1,67 @@
+//! Writes the JSON Schemas of resource types, see `resourcelib_ffi::schema`.
+//!
+//! ```text
+//! glacier-schema <hm2016|hm2|hm3>                          lists the resource types
+//! glacier-schema <hm2016|hm2|hm3> <resource type>          prints the schema of a resource type
+//! glacier-schema <hm2016|hm2|hm3> --all <directory>        writes <resource type>.schema.json for every type
+//! ```
+
+use std::path::Path;
+use std::process::ExitCode;
+use resourcelib_ffi::schema::{resource_types, schema_for};
+use resourcelib_ffi::WoaVersion;
+
+const USAGE: &str = "Usage: glacier-schema <hm2016|hm2|hm3> [<resource type> | --all <directory>]";
+
+fn parse_version(version: &str) -> Option<WoaVersion> {
+    match version.to_ascii_lowercase().as_str() {
+        "hm2016" => Some(WoaVersion::HM2016),
+        "hm2" => Some(WoaVersion::HM2),
+        "hm3" => Some(WoaVersion::HM3),
+        _ => None,
+    }
+}
+
+fn write_all(version: WoaVersion, directory: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(directory).map_err(|error| error.to_string())?;
+    for resource_type in resource_types(version) {
+        let schema = schema_for(version, resource_type).ok_or_else(|| format!("No schema for {}", resource_type))?;
+        let path = directory.join(format!("{}.schema.json", resource_type));
+        let json = serde_json::to_string_pretty(&schema).map_err(|error| error.to_string())?;
+        std::fs::write(&path, json).map_err(|error| format!("{}: {}", path.display(), error))?;
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let version = args.first()
+        .and_then(|version| parse_version(version))
+        .ok_or_else(|| USAGE.to_owned())?;
+
+    match &args[1..] {
+        [] => {
+            resource_types(version).iter().for_each(|resource_type| println!("{}", resource_type));
+            Ok(())
+        }
+        [flag, directory] if flag == "--all" => write_all(version, Path::new(directory)),
+        [resource_type] => {
+            let schema = schema_for(version, resource_type)
+                .ok_or_else(|| format!("{} isn't a resource type of {:?} with the enabled features", resource_type, version))?;
+            println!("{}", serde_json::to_string_pretty(&schema).map_err(|error| error.to_string())?);
+            Ok(())
+        }
+        _ => Err(USAGE.to_owned()),
+    }
+}
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -2,7 +2,13 @@ use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::path::Path;
 use crate::{ResourceConverter, ResourceGenerator, ResourceLibError, WoaVersion};
-use glacier_codegen::{hm2016_bindings, hm2_bindings, hm3_bindings};
+#[cfg(feature = "hm2016")]
+use glacier_hm2016 as hm2016_bindings;
+#[cfg(feature = "hm2")]
+use glacier_hm2 as hm2_bindings;
+#[cfg(feature = "hm3")]
+use glacier_hm3 as hm3_bindings;
+use glacier_types::ir::TypeModel;
 
 pub trait ResourceLibResource : serde::Serialize + for<'a> serde::Deserialize<'a> + Debug{
     fn get_version() -> WoaVersion;
@@ -24,34 +30,81 @@ macro_rules! register_resource {
 }
 
 //hitman 2016
+#[cfg(all(feature = "hm2016", feature = "temp"))]
 register_resource!(hm2016_bindings::properties::STemplateEntity, WoaVersion::HM2016, "TEMP");
+#[cfg(all(feature = "hm2016", feature = "tblu"))]
 register_resource!(hm2016_bindings::properties::STemplateEntityBlueprint, WoaVersion::HM2016, "TBLU");
+#[cfg(all(feature = "hm2016", feature = "airg"))]
 register_resource!(hm2016_bindings::properties::SReasoningGrid, WoaVersion::HM2016, "AIRG");
+#[cfg(all(feature = "hm2016", feature = "atmd"))]
 register_resource!(hm2016_bindings::properties::ZamdTake, WoaVersion::HM2016, "ATMD");
+#[cfg(all(feature = "hm2016", feature = "vidb"))]
 register_resource!(hm2016_bindings::properties::SVideoDatabaseData, WoaVersion::HM2016, "VIDB");
+#[cfg(all(feature = "hm2016", feature = "cblu"))]
 register_resource!(hm2016_bindings::properties::SCppEntityBlueprint, WoaVersion::HM2016, "CBLU");
+#[cfg(all(feature = "hm2016", feature = "cppt"))]
 register_resource!(hm2016_bindings::properties::SCppEntity, WoaVersion::HM2016, "CPPT");
+#[cfg(all(feature = "hm2016", feature = "crmd"))]
 register_resource!(hm2016_bindings::properties::SCrowdMapData, WoaVersion::HM2016, "CRMD");
+#[cfg(all(feature = "hm2016", feature = "gidx"))]
+register_resource!(hm2016_bindings::properties::SResourceIndex, WoaVersion::HM2016, "GIDX");
 
 //hitman 2
+#[cfg(all(feature = "hm2", feature = "temp"))]
 register_resource!(hm2_bindings::properties::STemplateEntityFactory, WoaVersion::HM2, "TEMP");
+#[cfg(all(feature = "hm2", feature = "tblu"))]
 register_resource!(hm2_bindings::properties::STemplateEntityBlueprint, WoaVersion::HM2, "TBLU");
+#[cfg(all(feature = "hm2", feature = "airg"))]
 register_resource!(hm2_bindings::properties::SReasoningGrid, WoaVersion::HM2, "AIRG");
+#[cfg(all(feature = "hm2", feature = "atmd"))]
 register_resource!(hm2_bindings::properties::ZamdTake, WoaVersion::HM2, "ATMD");
+#[cfg(all(feature = "hm2", feature = "vidb"))]
 register_resource!(hm2_bindings::properties::SVideoDatabaseData, WoaVersion::HM2, "VIDB");
+#[cfg(all(feature = "hm2", feature = "cblu"))]
 register_resource!(hm2_bindings::properties::SCppEntityBlueprint, WoaVersion::HM2, "CBLU");
+#[cfg(all(feature = "hm2", feature = "cppt"))]
 register_resource!(hm2_bindings::properties::SCppEntity, WoaVersion::HM2, "CPPT");
+#[cfg(all(feature = "hm2", feature = "crmd"))]
 register_resource!(hm2_bindings::properties::SCrowdMapData, WoaVersion::HM2, "CRMD");
+#[cfg(all(feature = "hm2", feature = "gidx"))]
+register_resource!(hm2_bindings::properties::SResourceIndex, WoaVersion::HM2, "GIDX");
 
 //hitman 3
+#[cfg(all(feature = "hm3", feature = "temp"))]
 register_resource!(hm3_bindings::properties::STemplateEntityFactory, WoaVersion::HM3, "TEMP");
+#[cfg(all(feature = "hm3", feature = "tblu"))]
 register_resource!(hm3_bindings::properties::STemplateEntityBlueprint, WoaVersion::HM3, "TBLU");
+#[cfg(all(feature = "hm3", feature = "airg"))]
 register_resource!(hm3_bindings::properties::SReasoningGrid, WoaVersion::HM3, "AIRG");
+#[cfg(all(feature = "hm3", feature = "atmd"))]
 register_resource!(hm3_bindings::properties::ZamdTake, WoaVersion::HM3, "ATMD");
+#[cfg(all(feature = "hm3", feature = "vidb"))]
 register_resource!(hm3_bindings::properties::SVideoDatabaseData, WoaVersion::HM3, "VIDB");
+#[cfg(all(feature = "hm3", feature = "cblu"))]
 register_resource!(hm3_bindings::properties::SCppEntityBlueprint, WoaVersion::HM3, "CBLU");
+#[cfg(all(feature = "hm3", feature = "cppt"))]
 register_resource!(hm3_bindings::properties::SCppEntity, WoaVersion::HM3, "CPPT");
+#[cfg(all(feature = "hm3", feature = "crmd"))]
 register_resource!(hm3_bindings::properties::SCrowdMapData, WoaVersion::HM3, "CRMD");
+#[cfg(all(feature = "hm3", feature = "gidx"))]
+register_resource!(hm3_bindings::properties::SResourceIndex, WoaVersion::HM3, "GIDX");
+
+/// The classes and enums of a game version, as extracted from its ZHMGen.h by glacier-codegen.
+///
+/// Returns `None` if the feature of the version is disabled. Without the `all-types` feature, the model only contains
+/// the types reachable from the enabled resource types.
+pub fn type_model(version: WoaVersion) -> Option<&'static TypeModel> {
+    match version {
+        #[cfg(feature = "hm2016")]
+        WoaVersion::HM2016 => Some(hm2016_bindings::type_model()),
+        #[cfg(feature = "hm2")]
+        WoaVersion::HM2 => Some(hm2_bindings::type_model()),
+        #[cfg(feature = "hm3")]
+        WoaVersion::HM3 => Some(hm3_bindings::type_model()),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct ResourceParserTyped<T: ResourceLibResource> {
@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use glacier_types::{EntityTemplatePropertyId, ZVariant};
+#[cfg(feature = "hm2016")]
+use glacier_hm2016 as hm2016_bindings;
+#[cfg(feature = "hm2")]
+use glacier_hm2 as hm2_bindings;
+#[cfg(feature = "hm3")]
+use glacier_hm3 as hm3_bindings;
+use crate::query::property_id_from_name;
+#[cfg(feature = "native")]
+use crate::{ResourceLib, WoaVersion};
+use crate::ResourceLibError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CppSubset {
+    pub name: String,
+    pub type_name: String,
+    pub flags: u32,
+}
+
+pub trait CppBlueprintData {
+    fn type_name(&self) -> &str;
+    fn subsets(&self) -> Vec<CppSubset>;
+}
+
+pub trait CppEntityData {
+    /// The index of the CBLU of this entity in the references of the CPPT.
+    fn blueprint_index(&self) -> i32;
+    fn properties(&self) -> Vec<(&EntityTemplatePropertyId, &dyn ZVariant)>;
+}
+
+macro_rules! impl_cpp_entity {
+    ($bindings:ident) => {
+        impl CppBlueprintData for $bindings::properties::SCppEntityBlueprint {
+            fn type_name(&self) -> &str {
+                self.type_name.as_str()
+            }
+
+            fn subsets(&self) -> Vec<CppSubset> {
+                self.subsets.iter()
+                    .map(|subset| CppSubset { name: subset.name.to_string(), type_name: subset.type_.to_string(), flags: subset.flags })
+                    .collect()
+            }
+        }
+
+        impl CppEntityData for $bindings::properties::SCppEntity {
+            fn blueprint_index(&self) -> i32 {
+                self.blueprint_index_in_resource_header
+            }
+
+            fn properties(&self) -> Vec<(&EntityTemplatePropertyId, &dyn ZVariant)> {
+                self.property_values.iter().map(|property| (&property.n_property_id, property.value.as_ref())).collect()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "hm2016")]
+impl_cpp_entity!(hm2016_bindings);
+#[cfg(feature = "hm2")]
+impl_cpp_entity!(hm2_bindings);
+#[cfg(feature = "hm3")]
+impl_cpp_entity!(hm3_bindings);
+
+/// Resolves property names through ResourceLib's `GetPropertyName`. Unknown IDs resolve to `None`.
+#[cfg(feature = "native")]
+pub fn resourcelib_property_names(version: WoaVersion) -> impl Fn(u32) -> Option<String> {
+    move |id| ResourceLib::property_name(version, id).ok().flatten()
+}
+
+/// The expected value type of a property of a C++ entity type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyDeclaration {
+    pub name: Option<String>,
+    /// The ZVariant type name, e.g. `float32` or `TArray<ZString>`.
+    pub type_name: String,
+}
+
+/// Property declarations per C++ entity type, keyed by the type name of the CBLU and the property ID.
+///
+/// A CBLU only stores the type name and subsets of its entity, so declarations are either registered by hand or
+/// learned from CPPT resources known to be correct.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PropertyDeclarations {
+    types: BTreeMap<String, BTreeMap<u32, PropertyDeclaration>>,
+}
+
+impl PropertyDeclarations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn declare(&mut self, entity_type: &str, property: &str, type_name: &str) {
+        self.types.entry(entity_type.to_owned()).or_default().insert(
+            property_id_from_name(property),
+            PropertyDeclaration { name: Some(property.to_owned()), type_name: type_name.to_owned() },
+        );
+    }
+
+    /// Declares every property set by a CPPT that isn't declared yet, using the type of its value.
+    pub fn learn<B: CppBlueprintData, E: CppEntityData>(&mut self, blueprint: &B, entity: &E, resolve_name: impl Fn(u32) -> Option<String>) {
+        let declarations = self.types.entry(blueprint.type_name().to_owned()).or_default();
+        for (index, (id, value)) in entity.properties().into_iter().enumerate() {
+            let property = CppProperty::new(index, id, value, &resolve_name);
+            declarations.entry(property.id).or_insert(PropertyDeclaration { name: property.name, type_name: property.type_name });
+        }
+    }
+
+    pub fn get(&self, entity_type: &str, property_id: u32) -> Option<&PropertyDeclaration> {
+        self.types.get(entity_type).and_then(|declarations| declarations.get(&property_id))
+    }
+
+    pub fn properties(&self, entity_type: &str) -> impl Iterator<Item = (u32, &PropertyDeclaration)> {
+        self.types.get(entity_type).into_iter().flat_map(|declarations| declarations.iter().map(|(id, declaration)| (*id, declaration)))
+    }
+}
+
+/// A property value of a CPPT, with its ID and name both resolved.
+pub struct CppProperty<'a> {
+    pub index: usize,
+    pub id: u32,
+    pub name: Option<String>,
+    pub type_name: String,
+    pub value: &'a dyn ZVariant,
+}
+
+impl<'a> CppProperty<'a> {
+    fn new(index: usize, id: &EntityTemplatePropertyId, value: &'a dyn ZVariant, resolve_name: &impl Fn(u32) -> Option<String>) -> Self {
+        let (id, name) = match id {
+            EntityTemplatePropertyId::Num(id) => (*id, resolve_name(*id)),
+            EntityTemplatePropertyId::Str(name) => (property_id_from_name(name), Some(name.clone())),
+        };
+        let type_name = value.type_name().to_owned();
+        CppProperty { index, id, name, type_name, value }
+    }
+
+    /// The name of the property, or its ID in hexadecimal if the name is unknown.
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| format!("{:#010x}", self.id))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertyMismatch {
+    /// The value has a different type than the declaration.
+    Type { index: usize, property: String, expected: String, found: String },
+    /// The entity type doesn't declare the property.
+    Undeclared { index: usize, property: String },
+    /// The property was already set earlier in the same CPPT.
+    Duplicate { index: usize, property: String },
+}
+
+impl Display for PropertyMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PropertyMismatch::Type { index, property, expected, found } => write!(f, "propertyValues[{}] {}: expected {}, found {}", index, property, expected, found),
+            PropertyMismatch::Undeclared { index, property } => write!(f, "propertyValues[{}] {}: not declared", index, property),
+            PropertyMismatch::Duplicate { index, property } => write!(f, "propertyValues[{}] {}: set more than once", index, property),
+        }
+    }
+}
+
+/// A CPPT joined with its CBLU.
+pub struct CppEntityReflection<'a> {
+    pub type_name: String,
+    pub subsets: Vec<CppSubset>,
+    pub properties: Vec<CppProperty<'a>>,
+}
+
+impl<'a> CppEntityReflection<'a> {
+    /// Joins a CPPT with the CBLU it references, resolving property names with `resolve_name`.
+    ///
+    /// Use [`resourcelib_property_names`] to resolve names through ResourceLib.
+    pub fn new<B: CppBlueprintData, E: CppEntityData>(blueprint: &B, entity: &'a E, resolve_name: impl Fn(u32) -> Option<String>) -> Self {
+        CppEntityReflection {
+            type_name: blueprint.type_name().to_owned(),
+            subsets: blueprint.subsets(),
+            properties: entity.properties().into_iter().enumerate()
+                .map(|(index, (id, value))| CppProperty::new(index, id, value, &resolve_name))
+                .collect(),
+        }
+    }
+
+    pub fn property(&self, name: &str) -> Option<&CppProperty<'a>> {
+        let id = property_id_from_name(name);
+        self.properties.iter().find(|property| property.id == id)
+    }
+
+    /// Checks every property value against the declarations of the entity type.
+    pub fn validate(&self, declarations: &PropertyDeclarations) -> Vec<PropertyMismatch> {
+        let mut mismatches = Vec::new();
+        for (position, property) in self.properties.iter().enumerate() {
+            let name = property.display_name();
+            if self.properties[..position].iter().any(|earlier| earlier.id == property.id) {
+                mismatches.push(PropertyMismatch::Duplicate { index: property.index, property: name });
+                continue;
+            }
+            match declarations.get(&self.type_name, property.id) {
+                None => mismatches.push(PropertyMismatch::Undeclared { index: property.index, property: name }),
+                Some(declaration) if declaration.type_name != property.type_name => mismatches.push(PropertyMismatch::Type {
+                    index: property.index,
+                    property: name,
+                    expected: declaration.type_name.clone(),
+                    found: property.type_name.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        mismatches
+    }
+
+    /// Writes the properties of the entity and any mismatches against the declarations.
+    pub fn write_report<W: Write>(&self, writer: &mut W, declarations: &PropertyDeclarations) -> Result<(), ResourceLibError> {
+        writeln!(writer, "{} ({} subsets, {} properties)", self.type_name, self.subsets.len(), self.properties.len())?;
+        for property in &self.properties {
+            writeln!(writer, "  {}: {}", property.display_name(), property.type_name)?;
+        }
+        let mismatches = self.validate(declarations);
+        writeln!(writer, "{} mismatches", mismatches.len())?;
+        for mismatch in mismatches {
+            writeln!(writer, "  {}", mismatch)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+use std::io::{BufRead, Write};
+#[cfg(feature = "hm2016")]
+use glacier_hm2016 as hm2016_bindings;
+#[cfg(feature = "hm2")]
+use glacier_hm2 as hm2_bindings;
+#[cfg(feature = "hm3")]
+use glacier_hm3 as hm3_bindings;
+use crate::ResourceLibError;
+
+/// The flag bit marking a crowd map cell as walkable.
+pub const WALKABLE: u8 = 1;
+
+/// A version independent copy of the cell grid of a crowd map (CRMD).
+///
+/// Cells are stored row by row, starting at the minimum corner of the map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrowdMap {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+    pub cell_size: f32,
+    pub width: usize,
+    pub height: usize,
+    pub cells: Vec<u8>,
+}
+
+impl CrowdMap {
+    fn index(&self, column: usize, row: usize) -> Option<usize> {
+        (column < self.width && row < self.height).then_some(row * self.width + column)
+    }
+
+    /// Finds the cell containing a world position, as `(column, row)`.
+    pub fn cell_at(&self, x: f32, y: f32) -> Option<(usize, usize)> {
+        let column = (x - self.min[0]) / self.cell_size;
+        let row = (y - self.min[1]) / self.cell_size;
+        if column < 0.0 || row < 0.0 {
+            return None;
+        }
+        let (column, row) = (column as usize, row as usize);
+        self.index(column, row).map(|_| (column, row))
+    }
+
+    /// The world position of the center of a cell.
+    pub fn cell_center(&self, column: usize, row: usize) -> [f32; 2] {
+        [
+            self.min[0] + (column as f32 + 0.5) * self.cell_size,
+            self.min[1] + (row as f32 + 0.5) * self.cell_size,
+        ]
+    }
+
+    pub fn flags(&self, column: usize, row: usize) -> Option<u8> {
+        self.index(column, row).map(|index| self.cells[index])
+    }
+
+    pub fn set_flags(&mut self, column: usize, row: usize, flags: u8) -> Result<(), ResourceLibError> {
+        let index = self.index(column, row).ok_or(ResourceLibError::InvalidCell(column, row))?;
+        self.cells[index] = flags;
+        Ok(())
+    }
+
+    pub fn flags_at(&self, x: f32, y: f32) -> Option<u8> {
+        self.cell_at(x, y).and_then(|(column, row)| self.flags(column, row))
+    }
+
+    pub fn is_walkable(&self, column: usize, row: usize) -> bool {
+        self.flags(column, row).is_some_and(|flags| flags & WALKABLE != 0)
+    }
+
+    pub fn is_walkable_at(&self, x: f32, y: f32) -> bool {
+        self.flags_at(x, y).is_some_and(|flags| flags & WALKABLE != 0)
+    }
+
+    pub fn set_walkable(&mut self, column: usize, row: usize, walkable: bool) -> Result<(), ResourceLibError> {
+        let flags = self.flags(column, row).ok_or(ResourceLibError::InvalidCell(column, row))?;
+        self.set_flags(column, row, if walkable { flags | WALKABLE } else { flags & !WALKABLE })
+    }
+
+    pub fn walkable_cell_count(&self) -> usize {
+        self.cells.iter().filter(|&&flags| flags & WALKABLE != 0).count()
+    }
+
+    /// Writes one line per cell with its grid coordinates, world center and flags.
+    pub fn write_csv<W: Write>(&self, writer: &mut W) -> Result<(), ResourceLibError> {
+        writeln!(writer, "column,row,x,y,flags,walkable")?;
+        for row in 0..self.height {
+            for column in 0..self.width {
+                let [x, y] = self.cell_center(column, row);
+                let flags = self.cells[row * self.width + column];
+                writeln!(writer, "{},{},{},{},{},{}", column, row, x, y, flags, flags & WALKABLE != 0)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads cell flags back from a CSV file written by [`CrowdMap::write_csv`].
+    ///
+    /// Only the `column`, `row` and `flags` columns are used. Cells missing from the file keep their flags.
+    pub fn read_csv<R: BufRead>(&mut self, reader: R) -> Result<(), ResourceLibError> {
+        let mut lines = reader.lines();
+        let header = lines.next().ok_or(ResourceLibError::InvalidCsv(1, "header"))??;
+        let columns = header.split(',').map(str::trim).collect::<Vec<_>>();
+        let position = |name: &'static str| columns.iter().position(|column| *column == name).ok_or(ResourceLibError::InvalidCsv(1, name));
+        let (column_index, row_index, flags_index) = (position("column")?, position("row")?, position("flags")?);
+
+        for (line_index, line) in lines.enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            // The header is line 1.
+            let invalid = |name: &'static str| ResourceLibError::InvalidCsv(line_index + 2, name);
+            let values = line.split(',').map(str::trim).collect::<Vec<_>>();
+            let value = |at: usize, name: &'static str| values.get(at).copied().ok_or_else(|| invalid(name));
+            let column = value(column_index, "column")?.parse::<usize>().map_err(|_| invalid("column"))?;
+            let row = value(row_index, "row")?.parse::<usize>().map_err(|_| invalid("row"))?;
+            let flags = value(flags_index, "flags")?.parse::<u8>().map_err(|_| invalid("flags"))?;
+            self.set_flags(column, row, flags)?;
+        }
+        Ok(())
+    }
+
+    /// Converts the map to grayscale pixels, walkable cells white and others black, with the minimum corner at the
+    /// bottom left.
+    pub fn to_grayscale(&self) -> Vec<u8> {
+        (0..self.height).rev()
+            .flat_map(|row| self.cells[row * self.width..(row + 1) * self.width].iter())
+            .map(|&flags| if flags & WALKABLE != 0 { 255 } else { 0 })
+            .collect()
+    }
+
+    /// Writes the walkability of the map as a grayscale PNG image with one pixel per cell.
+    #[cfg(feature = "png")]
+    pub fn write_png<W: Write>(&self, writer: W) -> Result<(), ResourceLibError> {
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()
+            .and_then(|mut png_writer| png_writer.write_image_data(&self.to_grayscale()))
+            .map_err(|e| ResourceLibError::IoError(std::io::Error::other(e)))
+    }
+}
+
+pub trait CrowdMapData {
+    fn to_crowd_map(&self) -> Result<CrowdMap, ResourceLibError>;
+
+    /// Writes the cell flags of the map back, leaving the bounds and dimensions of the resource untouched.
+    fn apply_crowd_map(&mut self, map: &CrowdMap) -> Result<(), ResourceLibError>;
+}
+
+macro_rules! impl_crowd_map {
+    ($bindings:ident) => {
+        impl CrowdMapData for $bindings::properties::SCrowdMapData {
+            fn to_crowd_map(&self) -> Result<CrowdMap, ResourceLibError> {
+                let width = usize::try_from(self.n_cell_count_x).map_err(|_| ResourceLibError::ConversionFailed("to_crowd_map"))?;
+                let height = usize::try_from(self.n_cell_count_y).map_err(|_| ResourceLibError::ConversionFailed("to_crowd_map"))?;
+                if self.f_cell_size <= 0.0 {
+                    return Err(ResourceLibError::InvalidCellSize(self.f_cell_size));
+                }
+                if self.a_cell_flags.len() != width * height {
+                    return Err(ResourceLibError::ConversionFailed("to_crowd_map"));
+                }
+
+                Ok(CrowdMap {
+                    min: [self.v_min.x, self.v_min.y, self.v_min.z],
+                    max: [self.v_max.x, self.v_max.y, self.v_max.z],
+                    cell_size: self.f_cell_size,
+                    width,
+                    height,
+                    cells: self.a_cell_flags.clone(),
+                })
+            }
+
+            fn apply_crowd_map(&mut self, map: &CrowdMap) -> Result<(), ResourceLibError> {
+                if map.width as i32 != self.n_cell_count_x || map.height as i32 != self.n_cell_count_y || map.cells.len() != map.width * map.height {
+                    return Err(ResourceLibError::ConversionFailed("apply_crowd_map"));
+                }
+                self.a_cell_flags = map.cells.clone();
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(feature = "hm2016")]
+impl_crowd_map!(hm2016_bindings);
+#[cfg(feature = "hm2")]
+impl_crowd_map!(hm2_bindings);
+#[cfg(feature = "hm3")]
+impl_crowd_map!(hm3_bindings);
@@ -1,12 +1,38 @@
 #[cfg(feature = "codegen")]
 pub mod codegen;
+#[cfg(feature = "airg")]
+pub mod airg;
+#[cfg(feature = "atmd")]
+pub mod atmd;
+#[cfg(all(feature = "cppt", feature = "cblu"))]
+pub mod cppt;
+#[cfg(feature = "crmd")]
+pub mod crmd;
+#[cfg(all(feature = "temp", feature = "tblu"))]
+pub mod quickentity;
+#[cfg(feature = "codegen")]
+pub mod query;
+#[cfg(feature = "rust-backend")]
+pub mod rust_backend;
+#[cfg(feature = "codegen")]
+pub mod schema;
+#[cfg(all(feature = "glam", feature = "temp"))]
+pub mod transform;
+#[cfg(feature = "vidb")]
+pub mod vidb;
 
 
 
+#[cfg(not(any(feature = "native", feature = "rust-backend")))]
+compile_error!("resourcelib-ffi needs the `native` or the `rust-backend` feature");
+
+#[cfg(feature = "native")]
 extern crate resourcelib_sys;
 
 // Optionally, bring the necessary items into scope
+#[cfg(feature = "native")]
 use resourcelib_sys::*;
+#[cfg(feature = "native")]
 use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
 use std::path::Path;
@@ -47,6 +73,74 @@ pub enum ResourceLibError {
     #[cfg(feature = "codegen")]
     #[error("serde json error: {0}")]
     SerdeError(#[from] serde_json::Error),
+
+    #[cfg(feature = "codegen")]
+    #[error("Invalid entity reference: {0}")]
+    InvalidEntityReference(String),
+
+    #[cfg(feature = "codegen")]
+    #[error("Invalid resource reference index: {0}")]
+    InvalidResourceReference(i32),
+
+    #[cfg(feature = "codegen")]
+    #[error("Patch could not be applied: {0}")]
+    PatchConflict(String),
+
+    #[cfg(feature = "codegen")]
+    #[error("Invalid waypoint index: {0}")]
+    InvalidWaypoint(usize),
+
+    #[cfg(feature = "codegen")]
+    #[error("Too many waypoints, a reasoning grid holds at most {0}")]
+    TooManyWaypoints(usize),
+
+    #[cfg(feature = "codegen")]
+    #[error("Invalid neighbor direction: {0}")]
+    InvalidNeighborDirection(usize),
+
+    #[cfg(feature = "codegen")]
+    #[error("Invalid cell size: {0}")]
+    InvalidCellSize(f32),
+
+    #[cfg(feature = "codegen")]
+    #[error("The heatmap is empty")]
+    EmptyHeatmap,
+
+    #[cfg(feature = "codegen")]
+    #[error("Invalid crowd map cell: {0}, {1}")]
+    InvalidCell(usize, usize),
+
+    #[cfg(feature = "codegen")]
+    #[error("Invalid CSV at line {0}, field {1}")]
+    InvalidCsv(usize, &'static str),
+
+    #[cfg(feature = "codegen")]
+    #[error("Invalid CSV at line {0}, expected {1} fields")]
+    CsvFieldCount(usize, usize),
+
+    #[cfg(feature = "codegen")]
+    #[error("Duplicate video key: {0}")]
+    DuplicateVideoKey(String),
+
+    #[cfg(feature = "codegen")]
+    #[error("Video keys can't be empty")]
+    EmptyVideoKey,
+
+    #[cfg(feature = "codegen")]
+    #[error("Unknown video key: {0}")]
+    UnknownVideoKey(String),
+
+    #[cfg(feature = "codegen")]
+    #[error("Invalid animation event: track {0}, event {1}")]
+    InvalidAnimationEvent(usize, usize),
+
+    #[cfg(feature = "rust-backend")]
+    #[error("Invalid resource: {0}")]
+    InvalidResource(String),
+
+    #[cfg(feature = "rust-backend")]
+    #[error("Binary error: {0}")]
+    BinaryError(#[from] glacier_types::binary::BinaryError),
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -56,6 +150,7 @@ pub enum WoaVersion {
     HM3,
 }
 
+#[cfg(feature = "native")]
 fn prepare_resource_type(resource_type: &str) -> Result<CString, ResourceLibError> {
     if resource_type.len() != 4 {
         return Err(ResourceLibError::InvalidResourceType(
@@ -67,6 +162,7 @@ fn prepare_resource_type(resource_type: &str) -> Result<CString, ResourceLibErro
         .map_err(|_| ResourceLibError::InvalidResourceType(resource_type.to_string()))
 }
 
+#[cfg(feature = "native")]
 fn prepare_path_parameter<P: AsRef<Path>>(
     path: P,
     should_exit: bool,
@@ -84,8 +180,10 @@ fn prepare_path_parameter<P: AsRef<Path>>(
     })
 }
 
+#[cfg(feature = "native")]
 pub struct ResourceLib;
 
+#[cfg(feature = "native")]
 impl ResourceLib {
     pub fn supported_resource_types(
         woa_version: WoaVersion,
@@ -145,12 +243,81 @@ impl ResourceLib {
 
         Ok(result)
     }
+
+    /// Looks up the name of a property from its ID. Returns `None` if ResourceLib doesn't know the property.
+    pub fn property_name(
+        woa_version: WoaVersion,
+        property_id: u32,
+    ) -> Result<Option<String>, ResourceLibError> {
+        let view = unsafe {
+            match woa_version {
+                WoaVersion::HM2016 => HM2016_GetPropertyName(property_id),
+                WoaVersion::HM2 => HM2_GetPropertyName(property_id),
+                WoaVersion::HM3 => HM3_GetPropertyName(property_id),
+            }
+        };
+
+        if view.Data.is_null() || view.Size == 0 {
+            return Ok(None);
+        }
+
+        let bytes = unsafe { std::slice::from_raw_parts(view.Data as *const u8, view.Size) };
+        std::str::from_utf8(bytes)
+            .map(|name| Some(name.to_owned()))
+            .map_err(ResourceLibError::Utf8Error)
+    }
+
+    /// Converts a structure in the memory layout of the game to JSON. Returns `None` if ResourceLib doesn't know the
+    /// type or expects a different size for it.
+    ///
+    /// # Safety
+    ///
+    /// `structure` has to be aligned for the type and contain a valid instance of it: ResourceLib follows the
+    /// pointers of arrays, strings and variants.
+    pub unsafe fn game_struct_to_json(
+        woa_version: WoaVersion,
+        type_name: &str,
+        structure: &[u8],
+    ) -> Result<Option<String>, ResourceLibError> {
+        let c_type_name = CString::new(type_name)
+            .map_err(|_| ResourceLibError::ConversionFailed("game_struct_to_json"))?;
+        let pointer = structure.as_ptr() as *const _;
+        let json_string_ptr = match woa_version {
+            WoaVersion::HM2016 => HM2016_GameStructToJson(c_type_name.as_ptr(), pointer, structure.len()),
+            WoaVersion::HM2 => HM2_GameStructToJson(c_type_name.as_ptr(), pointer, structure.len()),
+            WoaVersion::HM3 => HM3_GameStructToJson(c_type_name.as_ptr(), pointer, structure.len()),
+        };
+
+        if json_string_ptr.is_null() {
+            return Ok(None);
+        }
+
+        let json_string = *json_string_ptr;
+        let result = CStr::from_ptr(json_string.JsonData).to_string_lossy().into_owned();
+        match woa_version {
+            WoaVersion::HM2016 => HM2016_FreeJsonString(json_string_ptr),
+            WoaVersion::HM2 => HM2_FreeJsonString(json_string_ptr),
+            WoaVersion::HM3 => HM3_FreeJsonString(json_string_ptr),
+        }
+        Ok(Some(result))
+    }
 }
 
 /// Represents a resource converter.
+///
+/// [`ResourceConverter::new`] uses ResourceLib with the `native` feature. [`ResourceConverter::rust`] uses the pure-Rust
+/// implementation of the `rust-backend` feature, which returns errors instead of `false` when a conversion fails.
 #[derive(Debug, Clone)]
 pub struct ResourceConverter {
-    converter: *mut resourcelib_sys::ResourceConverter,
+    backend: ConverterBackend,
+}
+
+#[derive(Debug, Clone)]
+enum ConverterBackend {
+    #[cfg(feature = "native")]
+    Native(*mut resourcelib_sys::ResourceConverter),
+    #[cfg(feature = "rust-backend")]
+    Rust(rust_backend::RustResource),
 }
 
 unsafe impl Send for ResourceConverter {}
@@ -158,6 +325,7 @@ unsafe impl Sync for ResourceConverter {}
 
 impl ResourceConverter {
     /// Creates a new ResourceConverter for the specified resource type.
+    #[cfg(feature = "native")]
     pub fn new(version: WoaVersion, resource_type: &str) -> Result<Self, ResourceLibError> {
         let c_resource_type = prepare_resource_type(resource_type)?;
 
@@ -172,28 +340,54 @@ impl ResourceConverter {
             Err(ResourceLibError::NullPointer("created converter"))
         } else {
             Ok(ResourceConverter {
-                converter: converter_ptr,
+                backend: ConverterBackend::Native(converter_ptr),
             })
         }
     }
 
+    /// Creates a new ResourceConverter for the specified resource type, using the Rust backend.
+    #[cfg(not(feature = "native"))]
+    pub fn new(version: WoaVersion, resource_type: &str) -> Result<Self, ResourceLibError> {
+        Self::rust(version, resource_type)
+    }
+
+    /// Creates a new ResourceConverter for the specified resource type, implemented in Rust. Only the types of
+    /// [`rust_backend::supported_resource_types`] are supported.
+    #[cfg(feature = "rust-backend")]
+    pub fn rust(version: WoaVersion, resource_type: &str) -> Result<Self, ResourceLibError> {
+        Ok(ResourceConverter {
+            backend: ConverterBackend::Rust(rust_backend::RustResource::new(version, resource_type)?),
+        })
+    }
+
     /// Converts a resource file to a JSON file.
     pub fn resource_file_to_json_file<P: AsRef<Path>, Q: AsRef<Path>>(
         &self,
         resource_file_path: P,
         output_file_path: Q,
     ) -> Result<bool, ResourceLibError> {
-        let c_resource_file_path = prepare_path_parameter(resource_file_path, true)?;
-        let c_output_file_path = prepare_path_parameter(output_file_path, false)?;
-
-        unsafe {
-            let func = (*self.converter).FromResourceFileToJsonFile.ok_or(
-                ResourceLibError::ConverterFunctionError("FromResourceFileToJsonFile"),
-            )?;
-            Ok(func(
-                c_resource_file_path.as_ptr(),
-                c_output_file_path.as_ptr(),
-            ))
+        match &self.backend {
+            #[cfg(feature = "native")]
+            ConverterBackend::Native(converter) => {
+                let c_resource_file_path = prepare_path_parameter(resource_file_path, true)?;
+                let c_output_file_path = prepare_path_parameter(output_file_path, false)?;
+
+                unsafe {
+                    let func = (**converter).FromResourceFileToJsonFile.ok_or(
+                        ResourceLibError::ConverterFunctionError("FromResourceFileToJsonFile"),
+                    )?;
+                    Ok(func(
+                        c_resource_file_path.as_ptr(),
+                        c_output_file_path.as_ptr(),
+                    ))
+                }
+            }
+            #[cfg(feature = "rust-backend")]
+            ConverterBackend::Rust(resource) => {
+                let json = resource.convert(&std::fs::read(resource_file_path)?)?;
+                std::fs::write(output_file_path, json)?;
+                Ok(true)
+            }
         }
     }
 
@@ -203,38 +397,53 @@ impl ResourceConverter {
         resource_data: &[u8],
         output_file_path: P,
     ) -> Result<bool, ResourceLibError> {
-        let c_output_file_path = prepare_path_parameter(output_file_path, false)?;
-        unsafe {
-            let func = (*self.converter).FromMemoryToJsonFile.ok_or(
-                ResourceLibError::ConverterFunctionError("FromMemoryToJsonFile"),
-            )?;
-            Ok(func(
-                resource_data.as_ptr() as *const _,
-                resource_data.len(),
-                c_output_file_path.as_ptr(),
-            ))
+        match &self.backend {
+            #[cfg(feature = "native")]
+            ConverterBackend::Native(converter) => {
+                let c_output_file_path = prepare_path_parameter(output_file_path, false)?;
+                unsafe {
+                    let func = (**converter).FromMemoryToJsonFile.ok_or(
+                        ResourceLibError::ConverterFunctionError("FromMemoryToJsonFile"),
+                    )?;
+                    Ok(func(
+                        resource_data.as_ptr() as *const _,
+                        resource_data.len(),
+                        c_output_file_path.as_ptr(),
+                    ))
+                }
+            }
+            #[cfg(feature = "rust-backend")]
+            ConverterBackend::Rust(resource) => {
+                std::fs::write(output_file_path, resource.convert(resource_data)?)?;
+                Ok(true)
+            }
         }
     }
 
     /// Converts a resource from memory to a JSON string.
     pub fn memory_to_json_string(&self, resource_data: &[u8]) -> Result<String, ResourceLibError> {
-        unsafe {
-            let func = (*self.converter).FromMemoryToJsonString.ok_or(
-                ResourceLibError::ConverterFunctionError("FromMemoryToJsonString"),
-            )?;
-            let json_string_ptr = func(resource_data.as_ptr() as *const _, resource_data.len());
-            if json_string_ptr.is_null() {
-                Err(ResourceLibError::NullPointer("json result string"))
-            } else {
-                let json_string = *json_string_ptr;
-                let c_str = CStr::from_ptr(json_string.JsonData);
-                let result = c_str.to_string_lossy().into_owned();
-                let free_func = (*self.converter)
-                    .FreeJsonString
-                    .ok_or(ResourceLibError::ConverterFunctionError("FreeJsonString"))?;
-                free_func(json_string_ptr);
-                Ok(result)
-            }
+        match &self.backend {
+            #[cfg(feature = "native")]
+            ConverterBackend::Native(converter) => unsafe {
+                let func = (**converter).FromMemoryToJsonString.ok_or(
+                    ResourceLibError::ConverterFunctionError("FromMemoryToJsonString"),
+                )?;
+                let json_string_ptr = func(resource_data.as_ptr() as *const _, resource_data.len());
+                if json_string_ptr.is_null() {
+                    Err(ResourceLibError::NullPointer("json result string"))
+                } else {
+                    let json_string = *json_string_ptr;
+                    let c_str = CStr::from_ptr(json_string.JsonData);
+                    let result = c_str.to_string_lossy().into_owned();
+                    let free_func = (**converter)
+                        .FreeJsonString
+                        .ok_or(ResourceLibError::ConverterFunctionError("FreeJsonString"))?;
+                    free_func(json_string_ptr);
+                    Ok(result)
+                }
+            },
+            #[cfg(feature = "rust-backend")]
+            ConverterBackend::Rust(resource) => resource.convert(resource_data),
         }
     }
 
@@ -243,37 +452,57 @@ impl ResourceConverter {
         &self,
         resource_file_path: P,
     ) -> Result<String, ResourceLibError> {
-        let c_resource_file_path = prepare_path_parameter(resource_file_path, true)?;
-        unsafe {
-            let func = (*self.converter).FromResourceFileToJsonString.ok_or(ResourceLibError::ConverterFunctionError("FromResourceFileToJsonString"))?;
-            let json_string_ptr = func(c_resource_file_path.as_ptr());
-            if json_string_ptr.is_null() {
-                Err(ResourceLibError::NullPointer("json result string"))
-            } else {
-                let json_string = *json_string_ptr;
-                let c_str = CStr::from_ptr(json_string.JsonData);
-                let result = c_str.to_string_lossy().into_owned();
-                let free_func = (*self.converter)
-                    .FreeJsonString
-                    .ok_or(ResourceLibError::ConverterFunctionError("FreeJsonString"))?;
-                free_func(json_string_ptr);
-                Ok(result)
+        match &self.backend {
+            #[cfg(feature = "native")]
+            ConverterBackend::Native(converter) => {
+                let c_resource_file_path = prepare_path_parameter(resource_file_path, true)?;
+                unsafe {
+                    let func = (**converter).FromResourceFileToJsonString.ok_or(ResourceLibError::ConverterFunctionError("FromResourceFileToJsonString"))?;
+                    let json_string_ptr = func(c_resource_file_path.as_ptr());
+                    if json_string_ptr.is_null() {
+                        Err(ResourceLibError::NullPointer("json result string"))
+                    } else {
+                        let json_string = *json_string_ptr;
+                        let c_str = CStr::from_ptr(json_string.JsonData);
+                        let result = c_str.to_string_lossy().into_owned();
+                        let free_func = (**converter)
+                            .FreeJsonString
+                            .ok_or(ResourceLibError::ConverterFunctionError("FreeJsonString"))?;
+                        free_func(json_string_ptr);
+                        Ok(result)
+                    }
+                }
             }
+            #[cfg(feature = "rust-backend")]
+            ConverterBackend::Rust(resource) => resource.convert(&std::fs::read(resource_file_path)?),
         }
     }
 }
 
 /// Represents a resource generator.
+///
+/// Like [`ResourceConverter`], [`ResourceGenerator::new`] uses ResourceLib with the `native` feature and
+/// [`ResourceGenerator::rust`] uses the Rust backend. The Rust backend ignores `generate_compatible`.
 #[derive(Debug, Clone)]
 pub struct ResourceGenerator {
-    generator: *mut resourcelib_sys::ResourceGenerator,
+    backend: GeneratorBackend,
+}
+
+#[derive(Debug, Clone)]
+enum GeneratorBackend {
+    #[cfg(feature = "native")]
+    Native(*mut resourcelib_sys::ResourceGenerator),
+    #[cfg(feature = "rust-backend")]
+    Rust(rust_backend::RustResource),
 }
 
 unsafe impl Send for ResourceGenerator {}
 unsafe impl Sync for ResourceGenerator {}
 
+#[cfg_attr(not(feature = "native"), allow(unused_variables))]
 impl ResourceGenerator {
     /// Creates a new ResourceGenerator for the specified resource type.
+    #[cfg(feature = "native")]
     pub fn new(version: WoaVersion, resource_type: &str) -> Result<Self, ResourceLibError> {
         let c_resource_type = prepare_resource_type(resource_type)?;
         unsafe {
@@ -286,12 +515,27 @@ impl ResourceGenerator {
                 Err(ResourceLibError::NullPointer("created generator"))
             } else {
                 Ok(ResourceGenerator {
-                    generator: generator_ptr,
+                    backend: GeneratorBackend::Native(generator_ptr),
                 })
             }
         }
     }
 
+    /// Creates a new ResourceGenerator for the specified resource type, using the Rust backend.
+    #[cfg(not(feature = "native"))]
+    pub fn new(version: WoaVersion, resource_type: &str) -> Result<Self, ResourceLibError> {
+        Self::rust(version, resource_type)
+    }
+
+    /// Creates a new ResourceGenerator for the specified resource type, implemented in Rust. Only the types of
+    /// [`rust_backend::supported_resource_types`] are supported.
+    #[cfg(feature = "rust-backend")]
+    pub fn rust(version: WoaVersion, resource_type: &str) -> Result<Self, ResourceLibError> {
+        Ok(ResourceGenerator {
+            backend: GeneratorBackend::Rust(rust_backend::RustResource::new(version, resource_type)?),
+        })
+    }
+
     /// Generates a resource file from a JSON file.
     pub fn json_file_to_resource_file<P: AsRef<Path>, Q: AsRef<Path>>(
         &self,
@@ -299,18 +543,29 @@ impl ResourceGenerator {
         resource_file_path: Q,
         generate_compatible: bool,
     ) -> Result<bool, ResourceLibError> {
-        let c_json_file_path = prepare_path_parameter(json_file_path, true)?;
-        let c_resource_file_path = prepare_path_parameter(resource_file_path, false)?;
-
-        unsafe {
-            let func = (*self.generator).FromJsonFileToResourceFile.ok_or(
-                ResourceLibError::GeneratorFunctionError("FromJsonFileToResourceFile"),
-            )?;
-            Ok(func(
-                c_json_file_path.as_ptr(),
-                c_resource_file_path.as_ptr(),
-                generate_compatible,
-            ))
+        match &self.backend {
+            #[cfg(feature = "native")]
+            GeneratorBackend::Native(generator) => {
+                let c_json_file_path = prepare_path_parameter(json_file_path, true)?;
+                let c_resource_file_path = prepare_path_parameter(resource_file_path, false)?;
+
+                unsafe {
+                    let func = (**generator).FromJsonFileToResourceFile.ok_or(
+                        ResourceLibError::GeneratorFunctionError("FromJsonFileToResourceFile"),
+                    )?;
+                    Ok(func(
+                        c_json_file_path.as_ptr(),
+                        c_resource_file_path.as_ptr(),
+                        generate_compatible,
+                    ))
+                }
+            }
+            #[cfg(feature = "rust-backend")]
+            GeneratorBackend::Rust(resource) => {
+                let resource_data = resource.generate(&std::fs::read_to_string(json_file_path)?)?;
+                std::fs::write(resource_file_path, resource_data)?;
+                Ok(true)
+            }
         }
     }
 
@@ -321,17 +576,27 @@ impl ResourceGenerator {
         resource_file_path: P,
         generate_compatible: bool,
     ) -> Result<bool, ResourceLibError> {
-        let c_resource_file_path = prepare_path_parameter(resource_file_path, true)?;
-        unsafe {
-            let func = (*self.generator).FromJsonStringToResourceFile.ok_or(
-                ResourceLibError::GeneratorFunctionError("FromJsonStringToResourceFile"),
-            )?;
-            Ok(func(
-                json_str.as_ptr() as *const _,
-                json_str.len(),
-                c_resource_file_path.as_ptr(),
-                generate_compatible,
-            ))
+        match &self.backend {
+            #[cfg(feature = "native")]
+            GeneratorBackend::Native(generator) => {
+                let c_resource_file_path = prepare_path_parameter(resource_file_path, true)?;
+                unsafe {
+                    let func = (**generator).FromJsonStringToResourceFile.ok_or(
+                        ResourceLibError::GeneratorFunctionError("FromJsonStringToResourceFile"),
+                    )?;
+                    Ok(func(
+                        json_str.as_ptr() as *const _,
+                        json_str.len(),
+                        c_resource_file_path.as_ptr(),
+                        generate_compatible,
+                    ))
+                }
+            }
+            #[cfg(feature = "rust-backend")]
+            GeneratorBackend::Rust(resource) => {
+                std::fs::write(resource_file_path, resource.generate(json_str)?)?;
+                Ok(true)
+            }
         }
     }
 
@@ -341,24 +606,18 @@ impl ResourceGenerator {
         json_file_path: P,
         generate_compatible: bool,
     ) -> Result<Vec<u8>, ResourceLibError> {
-        let c_json_file_path = prepare_path_parameter(json_file_path, true)?;
-        unsafe {
-            let func = (*self.generator).FromJsonFileToResourceMem.ok_or(ResourceLibError::GeneratorFunctionError("FromJsonFileToResourceMem"))?;
-            let resource_mem_ptr = func(c_json_file_path.as_ptr(), generate_compatible);
-            if resource_mem_ptr.is_null() {
-                Err(ResourceLibError::NullPointer("created resource mem"))
-            } else {
-                let resource_mem = *resource_mem_ptr;
-                let data_slice = std::slice::from_raw_parts(
-                    resource_mem.ResourceData as *const u8,
-                    resource_mem.DataSize,
-                );
-                let result = data_slice.to_vec();
-
-                let free_func = (*self.generator).FreeResourceMem.ok_or(ResourceLibError::GeneratorFunctionError("FreeResourceMem"))?;
-                free_func(resource_mem_ptr);
-                Ok(result)
+        match &self.backend {
+            #[cfg(feature = "native")]
+            GeneratorBackend::Native(generator) => {
+                let c_json_file_path = prepare_path_parameter(json_file_path, true)?;
+                unsafe {
+                    let func = (**generator).FromJsonFileToResourceMem.ok_or(ResourceLibError::GeneratorFunctionError("FromJsonFileToResourceMem"))?;
+                    let resource_mem_ptr = func(c_json_file_path.as_ptr(), generate_compatible);
+                    native_resource_mem(*generator, resource_mem_ptr)
+                }
             }
+            #[cfg(feature = "rust-backend")]
+            GeneratorBackend::Rust(resource) => resource.generate(&std::fs::read_to_string(json_file_path)?),
         }
     }
 
@@ -368,27 +627,41 @@ impl ResourceGenerator {
         json_str: &str,
         generate_compatible: bool,
     ) -> Result<Vec<u8>, ResourceLibError> {
-        unsafe {
-            let func = (*self.generator).FromJsonStringToResourceMem.ok_or(ResourceLibError::GeneratorFunctionError("FromJsonStringToResourceMem"))?;
-            let resource_mem_ptr = func(
-                json_str.as_ptr() as *const _,
-                json_str.len(),
-                generate_compatible,
-            );
-            if resource_mem_ptr.is_null() {
-                Err(ResourceLibError::NullPointer("created resource mem"))
-            } else {
-                let resource_mem = *resource_mem_ptr;
-                let data_slice = std::slice::from_raw_parts(
-                    resource_mem.ResourceData as *const u8,
-                    resource_mem.DataSize,
+        match &self.backend {
+            #[cfg(feature = "native")]
+            GeneratorBackend::Native(generator) => unsafe {
+                let func = (**generator).FromJsonStringToResourceMem.ok_or(ResourceLibError::GeneratorFunctionError("FromJsonStringToResourceMem"))?;
+                let resource_mem_ptr = func(
+                    json_str.as_ptr() as *const _,
+                    json_str.len(),
+                    generate_compatible,
                 );
-                let result = data_slice.to_vec();
-
-                let free_func = (*self.generator).FreeResourceMem.ok_or(ResourceLibError::GeneratorFunctionError("FreeResourceMem"))?;
-                free_func(resource_mem_ptr);
-                Ok(result)
-            }
+                native_resource_mem(*generator, resource_mem_ptr)
+            },
+            #[cfg(feature = "rust-backend")]
+            GeneratorBackend::Rust(resource) => resource.generate(json_str),
         }
     }
 }
+
+/// Copies a resource generated by ResourceLib and frees it.
+#[cfg(feature = "native")]
+unsafe fn native_resource_mem(
+    generator: *mut resourcelib_sys::ResourceGenerator,
+    resource_mem_ptr: *mut ResourceMem,
+) -> Result<Vec<u8>, ResourceLibError> {
+    if resource_mem_ptr.is_null() {
+        Err(ResourceLibError::NullPointer("created resource mem"))
+    } else {
+        let resource_mem = *resource_mem_ptr;
+        let data_slice = std::slice::from_raw_parts(
+            resource_mem.ResourceData as *const u8,
+            resource_mem.DataSize,
+        );
+        let result = data_slice.to_vec();
+
+        let free_func = (*generator).FreeResourceMem.ok_or(ResourceLibError::GeneratorFunctionError("FreeResourceMem"))?;
+        free_func(resource_mem_ptr);
+        Ok(result)
+    }
+}
@@ -0,0 +1,322 @@
+use std::fmt::{Display, Formatter};
+use serde_json::Value;
+use glacier_types::{EntityTemplatePropertyId, ZVariant};
+#[cfg(all(feature = "hm2016", feature = "temp"))]
+use glacier_hm2016 as hm2016_bindings;
+#[cfg(all(feature = "hm2", feature = "temp"))]
+use glacier_hm2 as hm2_bindings;
+#[cfg(all(feature = "hm3", feature = "temp"))]
+use glacier_hm3 as hm3_bindings;
+#[cfg(all(feature = "temp", feature = "tblu"))]
+use crate::quickentity::EntityTemplate;
+
+/// The property list of a sub-entity a property was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyList {
+    PropertyValues,
+    PostInitPropertyValues,
+    PlatformSpecificPropertyValues,
+}
+
+/// The location of a property inside a template, e.g. `subEntities[3].postInitPropertyValues[1]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PropertyPath {
+    /// The name of the sub-entity list of the template, `entityTemplates` in HM2016 and `subEntities` later.
+    pub entities: &'static str,
+    pub entity_index: usize,
+    pub list: PropertyList,
+    pub property_index: usize,
+}
+
+impl Display for PropertyPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.list {
+            PropertyList::PropertyValues => write!(f, "{}[{}].propertyValues[{}]", self.entities, self.entity_index, self.property_index),
+            PropertyList::PostInitPropertyValues => write!(f, "{}[{}].postInitPropertyValues[{}]", self.entities, self.entity_index, self.property_index),
+            PropertyList::PlatformSpecificPropertyValues => write!(f, "{}[{}].platformSpecificPropertyValues[{}].propertyValue", self.entities, self.entity_index, self.property_index),
+        }
+    }
+}
+
+/// A version independent view of a single property of a sub-entity.
+pub struct PropertyView<'a> {
+    pub path: PropertyPath,
+    pub id: &'a EntityTemplatePropertyId,
+    pub value: &'a dyn ZVariant,
+}
+
+#[cfg(feature = "temp")]
+impl<'a> PropertyView<'a> {
+    fn new(path: PropertyPath, id: &'a EntityTemplatePropertyId, value: &'a dyn ZVariant) -> Self {
+        PropertyView { path, id, value }
+    }
+}
+
+/// A version independent view of a sub-entity, used to evaluate queries.
+pub struct EntityView<'a> {
+    pub index: usize,
+    pub name: Option<&'a str>,
+    pub parent_index: i32,
+    pub factory_index: i32,
+    pub factory: Option<&'a str>,
+    pub properties: Vec<PropertyView<'a>>,
+}
+
+pub trait EntitySource {
+    fn entity_views(&self) -> Vec<EntityView<'_>>;
+}
+
+#[cfg(feature = "temp")]
+macro_rules! impl_entity_source {
+    ($factory:ty, $sub_entities:ident, $list_name:literal $(, $platform_specific:ident)?) => {
+        impl EntitySource for $factory {
+            fn entity_views(&self) -> Vec<EntityView<'_>> {
+                self.$sub_entities.iter().enumerate().map(|(index, sub_entity)| {
+                    let path = |list, property_index| PropertyPath { entities: $list_name, entity_index: index, list, property_index };
+                    let mut properties = Vec::new();
+                    for (i, p) in sub_entity.property_values.iter().enumerate() {
+                        properties.push(PropertyView::new(path(PropertyList::PropertyValues, i), &p.n_property_id, p.value.as_ref()));
+                    }
+                    for (i, p) in sub_entity.post_init_property_values.iter().enumerate() {
+                        properties.push(PropertyView::new(path(PropertyList::PostInitPropertyValues, i), &p.n_property_id, p.value.as_ref()));
+                    }
+                    $(
+                    for (i, p) in sub_entity.$platform_specific.iter().enumerate() {
+                        properties.push(PropertyView::new(path(PropertyList::PlatformSpecificPropertyValues, i), &p.property_value.n_property_id, p.property_value.value.as_ref()));
+                    }
+                    )?
+
+                    EntityView {
+                        index,
+                        name: None,
+                        parent_index: sub_entity.logical_parent.entity_index,
+                        factory_index: sub_entity.entity_type_resource_index,
+                        factory: None,
+                        properties,
+                    }
+                }).collect()
+            }
+        }
+    };
+}
+
+#[cfg(all(feature = "hm2016", feature = "temp"))]
+impl_entity_source!(hm2016_bindings::properties::STemplateEntity, entity_templates, "entityTemplates");
+#[cfg(all(feature = "hm2", feature = "temp"))]
+impl_entity_source!(hm2_bindings::properties::STemplateEntityFactory, sub_entities, "subEntities", platform_specific_property_values);
+#[cfg(all(feature = "hm3", feature = "temp"))]
+impl_entity_source!(hm3_bindings::properties::STemplateEntityFactory, sub_entities, "subEntities", platform_specific_property_values);
+
+#[cfg(all(feature = "temp", feature = "tblu"))]
+macro_rules! impl_template_entity_source {
+    ($bindings:ident) => {
+        impl EntitySource for EntityTemplate<$bindings::properties::STemplateEntityFactory, $bindings::properties::STemplateEntityBlueprint> {
+            fn entity_views(&self) -> Vec<EntityView<'_>> {
+                let mut views = self.factory.entity_views();
+                for view in &mut views {
+                    view.name = self.blueprint.sub_entities.get(view.index).map(|sub_entity| sub_entity.entity_name.as_str());
+                    view.factory = usize::try_from(view.factory_index).ok()
+                        .and_then(|index| self.factory_meta.references.get(index))
+                        .map(|reference| reference.hash.as_str());
+                }
+                views
+            }
+        }
+    };
+}
+
+#[cfg(all(feature = "hm2", feature = "temp", feature = "tblu"))]
+impl_template_entity_source!(hm2_bindings);
+#[cfg(all(feature = "hm3", feature = "temp", feature = "tblu"))]
+impl_template_entity_source!(hm3_bindings);
+
+/// Computes the CRC32 used by the engine to derive property IDs from property names.
+pub fn property_id_from_name(name: &str) -> u32 {
+    let mut crc = !0u32;
+    for byte in name.bytes() {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PropertyKey {
+    Any,
+    Name(String),
+    Id(u32),
+}
+
+/// Matches a single property by name or ID, and optionally by value type and value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyFilter {
+    key: PropertyKey,
+    value_type: Option<String>,
+    value: Option<Value>,
+}
+
+impl PropertyFilter {
+    /// Matches any property.
+    pub fn any() -> Self {
+        PropertyFilter { key: PropertyKey::Any, value_type: None, value: None }
+    }
+
+    /// Matches properties stored by name, or by the ID derived from that name.
+    pub fn named(name: &str) -> Self {
+        PropertyFilter { key: PropertyKey::Name(name.to_owned()), value_type: None, value: None }
+    }
+
+    pub fn id(id: u32) -> Self {
+        PropertyFilter { key: PropertyKey::Id(id), value_type: None, value: None }
+    }
+
+    /// Restricts the match to values of the given ZVariant type, e.g. `SMatrix43` or `TArray<ZString>`.
+    pub fn of_type(mut self, value_type: &str) -> Self {
+        self.value_type = Some(value_type.to_owned());
+        self
+    }
+
+    /// Restricts the match to values equal to the given value in ResourceLib's JSON representation.
+    pub fn equals(mut self, value: Value) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    fn matches_id(&self, id: &EntityTemplatePropertyId) -> bool {
+        match (&self.key, id) {
+            (PropertyKey::Any, _) => true,
+            (PropertyKey::Name(name), EntityTemplatePropertyId::Str(property_name)) => name == property_name,
+            (PropertyKey::Name(name), EntityTemplatePropertyId::Num(property_id)) => property_id_from_name(name) == *property_id,
+            (PropertyKey::Id(id), EntityTemplatePropertyId::Num(property_id)) => id == property_id,
+            (PropertyKey::Id(id), EntityTemplatePropertyId::Str(property_name)) => property_id_from_name(property_name) == *id,
+        }
+    }
+
+    fn matches(&self, property: &PropertyView) -> bool {
+        if !self.matches_id(property.id) {
+            return false;
+        }
+        if self.value_type.is_none() && self.value.is_none() {
+            return true;
+        }
+        self.value_type.as_ref().is_none_or(|expected| expected == property.value.type_name())
+            && self.value.as_ref().is_none_or(|expected| {
+                serde_json::to_value(property.value).is_ok_and(|mut object| object["$val"].take() == *expected)
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ParentKey {
+    Index(i32),
+    NotLocal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FactoryKey {
+    Index(i32),
+    Hash(String),
+}
+
+/// A sub-entity that matched a query, with the paths of the properties that satisfied its property filters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryMatch {
+    pub entity_index: usize,
+    pub properties: Vec<PropertyPath>,
+}
+
+/// Finds sub-entities of a template by name, parent, factory and properties.
+///
+/// All filters have to match. Every property filter has to match at least one property of the entity.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EntityQuery {
+    name: Option<String>,
+    parent: Option<ParentKey>,
+    factory: Option<FactoryKey>,
+    properties: Vec<PropertyFilter>,
+}
+
+impl EntityQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches entities with the given name. Names are only known when querying a TEMP together with its TBLU.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_owned());
+        self
+    }
+
+    /// Matches entities whose logical parent is the entity at the given index.
+    pub fn parent(mut self, entity_index: usize) -> Self {
+        self.parent = Some(ParentKey::Index(entity_index as i32));
+        self
+    }
+
+    /// Matches entities without a local logical parent: their parent is null or in another template.
+    pub fn no_parent(mut self) -> Self {
+        self.parent = Some(ParentKey::NotLocal);
+        self
+    }
+
+    /// Matches entities whose factory is the resource reference at the given index.
+    pub fn factory_index(mut self, index: i32) -> Self {
+        self.factory = Some(FactoryKey::Index(index));
+        self
+    }
+
+    /// Matches entities whose factory has the given hash or path. Requires the meta references of the TEMP.
+    pub fn factory(mut self, hash: &str) -> Self {
+        self.factory = Some(FactoryKey::Hash(hash.to_owned()));
+        self
+    }
+
+    pub fn with_property(mut self, filter: PropertyFilter) -> Self {
+        self.properties.push(filter);
+        self
+    }
+
+    fn matches_entity(&self, entity: &EntityView) -> bool {
+        self.name.as_ref().is_none_or(|name| entity.name == Some(name.as_str()))
+            && self.parent.as_ref().is_none_or(|parent| match parent {
+                ParentKey::Index(index) => entity.parent_index == *index,
+                ParentKey::NotLocal => entity.parent_index < 0,
+            })
+            && self.factory.as_ref().is_none_or(|factory| match factory {
+                FactoryKey::Index(index) => entity.factory_index == *index,
+                FactoryKey::Hash(hash) => entity.factory.is_some_and(|factory| factory.eq_ignore_ascii_case(hash)),
+            })
+    }
+
+    /// Runs the query, returning the matching entities in template order.
+    pub fn run<S: EntitySource + ?Sized>(&self, source: &S) -> Vec<QueryMatch> {
+        source.entity_views().iter()
+            .filter(|entity| self.matches_entity(entity))
+            .filter_map(|entity| {
+                let mut properties = Vec::new();
+                for filter in &self.properties {
+                    let matched = entity.properties.iter()
+                        .filter(|property| filter.matches(property))
+                        .map(|property| property.path)
+                        .collect::<Vec<_>>();
+                    if matched.is_empty() {
+                        return None;
+                    }
+                    for path in matched {
+                        if !properties.contains(&path) {
+                            properties.push(path);
+                        }
+                    }
+                }
+                Some(QueryMatch { entity_index: entity.index, properties })
+            })
+            .collect()
+    }
+
+    /// Runs the query and returns only the indices of the matching entities.
+    pub fn entity_indices<S: EntitySource + ?Sized>(&self, source: &S) -> Vec<usize> {
+        self.run(source).into_iter().map(|found| found.entity_index).collect()
+    }
+}
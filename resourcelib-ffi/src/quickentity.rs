@@ -0,0 +1,1414 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use glacier_types::{EntityTemplatePropertyId, ZVariant};
+#[cfg(feature = "hm2")]
+use glacier_hm2 as hm2_bindings;
+#[cfg(feature = "hm3")]
+use glacier_hm3 as hm3_bindings;
+use crate::ResourceLibError;
+
+pub const QUICK_ENTITY_VERSION: f64 = 3.1;
+pub const PATCH_VERSION: u8 = 6;
+
+const DEFAULT_FACTORY_FLAG: &str = "1F";
+const DEFAULT_RESOURCE_FLAG: &str = "5F";
+
+/// A single entry of a resource's reference list, as found in its meta file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceReference {
+    pub hash: String,
+    pub flag: String,
+}
+
+/// The parts of a resource meta file needed to resolve the indices stored in TEMP and TBLU resources.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceMeta {
+    pub hash: String,
+    pub references: Vec<ResourceReference>,
+}
+
+/// A TEMP/TBLU pair together with the meta references of both resources.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityTemplate<F, B> {
+    pub factory: F,
+    pub factory_meta: ResourceMeta,
+    pub blueprint: B,
+    pub blueprint_meta: ResourceMeta,
+}
+
+#[cfg(feature = "hm2")]
+pub type Hm2EntityTemplate = EntityTemplate<hm2_bindings::properties::STemplateEntityFactory, hm2_bindings::properties::STemplateEntityBlueprint>;
+#[cfg(feature = "hm3")]
+pub type Hm3EntityTemplate = EntityTemplate<hm3_bindings::properties::STemplateEntityFactory, hm3_bindings::properties::STemplateEntityBlueprint>;
+
+pub trait QuickEntityConvert: Sized {
+    fn to_quick_entity(&self) -> Result<Entity, ResourceLibError>;
+    fn from_quick_entity(entity: &Entity) -> Result<Self, ResourceLibError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SubType {
+    Brick,
+    Scene,
+    Template,
+}
+
+impl SubType {
+    fn from_raw(value: i32) -> Self {
+        match value {
+            2 => SubType::Brick,
+            1 => SubType::Scene,
+            _ => SubType::Template,
+        }
+    }
+
+    fn to_raw(self) -> i32 {
+        match self {
+            SubType::Brick => 2,
+            SubType::Scene => 1,
+            SubType::Template => 0,
+        }
+    }
+}
+
+/// A QuickEntity entity document.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entity {
+    pub temp_hash: String,
+    pub tblu_hash: String,
+    pub root_entity: String,
+    pub entities: BTreeMap<String, SubEntity>,
+    pub property_overrides: Vec<PropertyOverride>,
+    pub override_deletes: Vec<Ref>,
+    pub pin_connection_overrides: Vec<PinConnectionOverride>,
+    pub pin_connection_override_deletes: Vec<PinConnectionOverrideDelete>,
+    pub external_scenes: Vec<String>,
+    pub sub_type: SubType,
+    pub quick_entity_version: f64,
+    pub extra_factory_dependencies: Vec<Dependency>,
+    pub extra_blueprint_dependencies: Vec<Dependency>,
+    #[serde(default)]
+    pub comments: Vec<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubEntity {
+    pub parent: Ref,
+    pub name: String,
+    pub factory: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory_flag: Option<String>,
+    pub blueprint: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor_only: Option<bool>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, Property>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub platform_specific_properties: BTreeMap<String, BTreeMap<String, Property>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub events: BTreeMap<String, BTreeMap<String, Vec<RefMaybeConstantValue>>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub input_copying: BTreeMap<String, BTreeMap<String, Vec<RefMaybeConstantValue>>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub output_copying: BTreeMap<String, BTreeMap<String, Vec<RefMaybeConstantValue>>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub property_aliases: BTreeMap<String, Vec<PropertyAlias>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub exposed_entities: BTreeMap<String, ExposedEntity>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Property {
+    #[serde(rename = "type")]
+    pub property_type: String,
+    pub value: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_init: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimpleProperty {
+    #[serde(rename = "type")]
+    pub property_type: String,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyAlias {
+    pub original_property: String,
+    pub original_entity: Ref,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExposedEntity {
+    pub is_array: bool,
+    pub refers_to: Vec<Ref>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropertyOverride {
+    pub entities: Vec<Ref>,
+    pub properties: BTreeMap<String, SimpleProperty>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinConnectionOverride {
+    pub from_entity: Ref,
+    pub from_pin: String,
+    pub to_entity: Ref,
+    pub to_pin: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<SimpleProperty>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinConnectionOverrideDelete {
+    pub from_entity: Ref,
+    pub from_pin: String,
+    pub to_entity: Ref,
+    pub to_pin: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<SimpleProperty>,
+}
+
+/// A reference to an entity, either local (by ID), external (by ID and scene) or through an exposed entity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Ref {
+    Full(FullRef),
+    Short(Option<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FullRef {
+    #[serde(rename = "ref")]
+    pub entity_ref: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_scene: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exposed_entity: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RefMaybeConstantValue {
+    RefWithConstantValue(RefWithConstantValue),
+    Ref(Ref),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefWithConstantValue {
+    #[serde(rename = "ref")]
+    pub entity_ref: Ref,
+    pub value: SimpleProperty,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Dependency {
+    WithFlag(DependencyWithFlag),
+    Short(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DependencyWithFlag {
+    pub resource: String,
+    pub flag: String,
+}
+
+impl Dependency {
+    fn new(hash: &str, flag: &str, default_flag: &str) -> Self {
+        if flag == default_flag {
+            Dependency::Short(hash.to_owned())
+        } else {
+            Dependency::WithFlag(DependencyWithFlag { resource: hash.to_owned(), flag: flag.to_owned() })
+        }
+    }
+
+    fn hash_and_flag<'a>(&'a self, default_flag: &'a str) -> (&'a str, &'a str) {
+        match self {
+            Dependency::Short(hash) => (hash, default_flag),
+            Dependency::WithFlag(dependency) => (&dependency.resource, &dependency.flag),
+        }
+    }
+}
+
+/// A QuickEntity patch, describing the changes between two entity documents.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Patch {
+    pub temp_hash: String,
+    pub tblu_hash: String,
+    pub patch: Vec<PatchOperation>,
+    pub patch_version: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PatchOperation {
+    SetRootEntity(String),
+    SetSubType(SubType),
+    AddEntity(String, Box<SubEntity>),
+    RemoveEntityByID(String),
+    SubEntityOperation(String, SubEntityOperation),
+    AddPropertyOverride(PropertyOverride),
+    RemovePropertyOverride(PropertyOverride),
+    AddOverrideDelete(Ref),
+    RemoveOverrideDelete(Ref),
+    AddPinConnectionOverride(PinConnectionOverride),
+    RemovePinConnectionOverride(PinConnectionOverride),
+    AddPinConnectionOverrideDelete(PinConnectionOverrideDelete),
+    RemovePinConnectionOverrideDelete(PinConnectionOverrideDelete),
+    AddExternalScene(String),
+    RemoveExternalScene(String),
+    AddExtraFactoryDependency(Dependency),
+    RemoveExtraFactoryDependency(Dependency),
+    AddExtraBlueprintDependency(Dependency),
+    RemoveExtraBlueprintDependency(Dependency),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SubEntityOperation {
+    SetParent(Ref),
+    SetName(String),
+    SetFactory(String),
+    SetFactoryFlag(Option<String>),
+    SetBlueprint(String),
+    SetEditorOnly(Option<bool>),
+    AddProperty(String, Property),
+    RemovePropertyByName(String),
+    SetPropertyType(String, String),
+    SetPropertyValue { property_name: String, value: Value },
+    SetPropertyPostInit(String, bool),
+    AddPlatformSpecificProperty(String, String, Property),
+    RemovePlatformSpecificPropertyByName(String, String),
+    AddEventConnection(String, String, RefMaybeConstantValue),
+    RemoveEventConnection(String, String, RefMaybeConstantValue),
+    AddInputCopyingConnection(String, String, RefMaybeConstantValue),
+    RemoveInputCopyingConnection(String, String, RefMaybeConstantValue),
+    AddOutputCopyingConnection(String, String, RefMaybeConstantValue),
+    RemoveOutputCopyingConnection(String, String, RefMaybeConstantValue),
+    AddPropertyAliasConnection(String, PropertyAlias),
+    RemoveConnectionForPropertyAlias(String, PropertyAlias),
+    SetExposedEntity(String, ExposedEntity),
+    RemoveExposedEntity(String),
+}
+
+/// A version independent copy of `SEntityTemplateReference`.
+#[derive(Debug, Clone, PartialEq)]
+struct RawRef {
+    entity_id: u64,
+    external_scene_index: i32,
+    entity_index: i32,
+    exposed_entity: String,
+}
+
+impl RawRef {
+    fn from_json(value: &Value) -> Result<Self, ResourceLibError> {
+        Ok(RawRef {
+            entity_id: value["entityID"].as_u64().unwrap_or(u64::MAX),
+            external_scene_index: value["externalSceneIndex"].as_i64().unwrap_or(-1) as i32,
+            entity_index: value["entityIndex"].as_i64().ok_or_else(|| ResourceLibError::InvalidEntityReference(value.to_string()))? as i32,
+            exposed_entity: value["exposedEntity"].as_str().unwrap_or_default().to_owned(),
+        })
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "entityID": self.entity_id,
+            "externalSceneIndex": self.external_scene_index,
+            "entityIndex": self.entity_index,
+            "exposedEntity": self.exposed_entity,
+        })
+    }
+}
+
+/// The first value that occurs more than once.
+fn first_duplicate<T: Eq + Hash>(values: &[T]) -> Option<&T> {
+    let mut seen = HashSet::new();
+    values.iter().find(|&value| !seen.insert(value))
+}
+
+fn format_entity_id(entity_id: u64) -> String {
+    format!("{:016x}", entity_id)
+}
+
+fn parse_entity_id(entity_id: &str) -> Result<u64, ResourceLibError> {
+    u64::from_str_radix(entity_id, 16).map_err(|_| ResourceLibError::InvalidEntityReference(entity_id.to_owned()))
+}
+
+fn property_name(id: &EntityTemplatePropertyId) -> String {
+    match id {
+        EntityTemplatePropertyId::Str(name) => name.clone(),
+        EntityTemplatePropertyId::Num(id) => id.to_string(),
+    }
+}
+
+fn property_id(name: &str) -> EntityTemplatePropertyId {
+    match name.parse::<u32>() {
+        Ok(id) => EntityTemplatePropertyId::Num(id),
+        Err(_) => EntityTemplatePropertyId::Str(name.to_owned()),
+    }
+}
+
+fn variant_to_json(value: &dyn ZVariant) -> Result<(String, Value), ResourceLibError> {
+    let mut object = serde_json::to_value(value)?;
+    let property_type = object["$type"].as_str().unwrap_or("void").to_owned();
+    let value = object.get_mut("$val").map(Value::take).unwrap_or(Value::Null);
+    Ok((property_type, value))
+}
+
+fn json_to_variant(property_type: &str, value: Value) -> Result<Box<dyn ZVariant>, ResourceLibError> {
+    Ok(serde_json::from_value(json!({ "$type": property_type, "$val": value }))?)
+}
+
+fn array_inner_type(property_type: &str) -> Option<&str> {
+    property_type.strip_prefix("TArray<").and_then(|inner| inner.strip_suffix('>'))
+}
+
+fn resource_reference(meta: &ResourceMeta, index: i32) -> Result<&ResourceReference, ResourceLibError> {
+    usize::try_from(index).ok()
+        .and_then(|index| meta.references.get(index))
+        .ok_or(ResourceLibError::InvalidResourceReference(index))
+}
+
+/// Resolves the indices of a TEMP/TBLU pair while converting it to QuickEntity.
+struct ExportContext<'a> {
+    entity_ids: Vec<String>,
+    factory_meta: &'a ResourceMeta,
+    external_scenes: Vec<String>,
+}
+
+impl ExportContext<'_> {
+    fn local_entity(&self, index: i32) -> Result<String, ResourceLibError> {
+        usize::try_from(index).ok()
+            .and_then(|index| self.entity_ids.get(index))
+            .cloned()
+            .ok_or_else(|| ResourceLibError::InvalidEntityReference(format!("entity index {}", index)))
+    }
+
+    fn convert_ref(&self, raw: &RawRef) -> Result<Ref, ResourceLibError> {
+        let exposed_entity = (!raw.exposed_entity.is_empty()).then(|| raw.exposed_entity.clone());
+        match raw.entity_index {
+            -1 => Ok(Ref::Short(None)),
+            -2 => {
+                let external_scene = usize::try_from(raw.external_scene_index).ok()
+                    .and_then(|index| self.external_scenes.get(index))
+                    .cloned()
+                    .ok_or(ResourceLibError::InvalidResourceReference(raw.external_scene_index))?;
+                Ok(Ref::Full(FullRef {
+                    entity_ref: format_entity_id(raw.entity_id),
+                    external_scene: Some(external_scene),
+                    exposed_entity,
+                }))
+            }
+            index => {
+                let entity_ref = self.local_entity(index)?;
+                Ok(match exposed_entity {
+                    Some(exposed_entity) => Ref::Full(FullRef { entity_ref, external_scene: None, exposed_entity: Some(exposed_entity) }),
+                    None => Ref::Short(Some(entity_ref)),
+                })
+            }
+        }
+    }
+
+    fn convert_value(&self, property_type: &str, value: Value) -> Result<Value, ResourceLibError> {
+        if let Some(inner) = array_inner_type(property_type) {
+            let Value::Array(items) = value else {
+                return Ok(value);
+            };
+            return items.into_iter()
+                .map(|item| self.convert_value(inner, item))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array);
+        }
+
+        match property_type {
+            "SEntityTemplateReference" => Ok(serde_json::to_value(self.convert_ref(&RawRef::from_json(&value)?)?)?),
+            "ZRuntimeResourceID" => {
+                let high = value["m_IDHigh"].as_u64().unwrap_or(u32::MAX as u64);
+                let low = value["m_IDLow"].as_u64().unwrap_or(u32::MAX as u64);
+                if high == u32::MAX as u64 && low == u32::MAX as u64 {
+                    return Ok(Value::Null);
+                }
+                let reference = resource_reference(self.factory_meta, low as i32)?;
+                Ok(serde_json::to_value(Dependency::new(&reference.hash, &reference.flag, DEFAULT_RESOURCE_FLAG))?)
+            }
+            "SMatrix43" => Ok(matrix_to_quick_entity(&value)),
+            "SColorRGB" | "SColorRGBA" => Ok(color_to_quick_entity(&value)),
+            "ZGuid" => Ok(guid_to_quick_entity(&value)),
+            _ => Ok(value),
+        }
+    }
+
+    fn convert_property(&self, value: &dyn ZVariant, post_init: bool) -> Result<Property, ResourceLibError> {
+        let (property_type, value) = variant_to_json(value)?;
+        Ok(Property {
+            value: self.convert_value(&property_type, value)?,
+            property_type,
+            post_init: post_init.then_some(true),
+        })
+    }
+
+    fn convert_simple_property(&self, value: &dyn ZVariant) -> Result<SimpleProperty, ResourceLibError> {
+        let (property_type, value) = variant_to_json(value)?;
+        Ok(SimpleProperty { value: self.convert_value(&property_type, value)?, property_type })
+    }
+
+    fn convert_pin_target(&self, to_index: i32, value: &dyn ZVariant) -> Result<RefMaybeConstantValue, ResourceLibError> {
+        let entity_ref = Ref::Short(Some(self.local_entity(to_index)?));
+        let value = self.convert_simple_property(value)?;
+        Ok(if value.property_type == "void" {
+            RefMaybeConstantValue::Ref(entity_ref)
+        } else {
+            RefMaybeConstantValue::RefWithConstantValue(RefWithConstantValue { entity_ref, value })
+        })
+    }
+}
+
+/// Builds the reference lists and indices of a TEMP/TBLU pair while converting from QuickEntity.
+struct ImportContext<'a> {
+    entity: &'a Entity,
+    entity_indices: HashMap<&'a str, i32>,
+    factory_references: Vec<ResourceReference>,
+    blueprint_references: Vec<ResourceReference>,
+    /// The factory references of the external scenes, in the order of `entity.external_scenes`.
+    external_scene_type_indices: Vec<i32>,
+}
+
+impl<'a> ImportContext<'a> {
+    fn new(entity: &'a Entity) -> Self {
+        ImportContext {
+            entity,
+            entity_indices: entity.entities.keys().enumerate().map(|(i, id)| (id.as_str(), i as i32)).collect(),
+            factory_references: Vec::new(),
+            blueprint_references: Vec::new(),
+            external_scene_type_indices: Vec::new(),
+        }
+    }
+
+    fn add_reference(references: &mut Vec<ResourceReference>, hash: &str, flag: &str) -> i32 {
+        match references.iter().position(|reference| reference.hash == hash && reference.flag == flag) {
+            Some(index) => index as i32,
+            None => {
+                references.push(ResourceReference { hash: hash.to_owned(), flag: flag.to_owned() });
+                references.len() as i32 - 1
+            }
+        }
+    }
+
+    fn factory_reference(&mut self, hash: &str, flag: &str) -> i32 {
+        Self::add_reference(&mut self.factory_references, hash, flag)
+    }
+
+    fn blueprint_reference(&mut self, hash: &str, flag: &str) -> i32 {
+        Self::add_reference(&mut self.blueprint_references, hash, flag)
+    }
+
+    fn local_index(&self, entity_id: &str) -> Result<i32, ResourceLibError> {
+        self.entity_indices.get(entity_id).copied().ok_or_else(|| ResourceLibError::InvalidEntityReference(entity_id.to_owned()))
+    }
+
+    fn convert_ref(&self, entity_ref: &Ref) -> Result<RawRef, ResourceLibError> {
+        let (entity_id, external_scene, exposed_entity) = match entity_ref {
+            Ref::Short(None) => return Ok(RawRef { entity_id: u64::MAX, external_scene_index: -1, entity_index: -1, exposed_entity: String::new() }),
+            Ref::Short(Some(entity_id)) => (entity_id, None, None),
+            Ref::Full(full) => (&full.entity_ref, full.external_scene.as_ref(), full.exposed_entity.as_ref()),
+        };
+        let exposed_entity = exposed_entity.cloned().unwrap_or_default();
+        match external_scene {
+            Some(scene) => {
+                let external_scene_index = self.entity.external_scenes.iter()
+                    .position(|external_scene| external_scene == scene)
+                    .ok_or_else(|| ResourceLibError::InvalidEntityReference(format!("external scene {}", scene)))?;
+                // The index is only valid once the external scenes have been added to the factory references.
+                let registered = self.external_scene_type_indices.get(external_scene_index)
+                    .and_then(|&reference| usize::try_from(reference).ok())
+                    .is_some_and(|reference| reference < self.factory_references.len());
+                if !registered {
+                    return Err(ResourceLibError::InvalidResourceReference(external_scene_index as i32));
+                }
+                Ok(RawRef { entity_id: parse_entity_id(entity_id)?, external_scene_index: external_scene_index as i32, entity_index: -2, exposed_entity })
+            }
+            None => Ok(RawRef { entity_id: u64::MAX, external_scene_index: -1, entity_index: self.local_index(entity_id)?, exposed_entity }),
+        }
+    }
+
+    fn convert_value(&mut self, property_type: &str, value: &Value) -> Result<Value, ResourceLibError> {
+        if let Some(inner) = array_inner_type(property_type) {
+            let Value::Array(items) = value else {
+                return Ok(value.clone());
+            };
+            return items.iter()
+                .map(|item| self.convert_value(inner, item))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array);
+        }
+
+        match property_type {
+            "SEntityTemplateReference" => {
+                let entity_ref: Ref = serde_json::from_value(value.clone())?;
+                Ok(self.convert_ref(&entity_ref)?.to_json())
+            }
+            "ZRuntimeResourceID" => {
+                if value.is_null() {
+                    return Ok(json!({ "m_IDHigh": u32::MAX, "m_IDLow": u32::MAX }));
+                }
+                let dependency: Dependency = serde_json::from_value(value.clone())?;
+                let (hash, flag) = dependency.hash_and_flag(DEFAULT_RESOURCE_FLAG);
+                Ok(json!({ "m_IDHigh": 0, "m_IDLow": self.factory_reference(hash, flag) }))
+            }
+            "SMatrix43" => Ok(matrix_from_quick_entity(value)),
+            "SColorRGB" => color_from_quick_entity(value, false),
+            "SColorRGBA" => color_from_quick_entity(value, true),
+            "ZGuid" => guid_from_quick_entity(value),
+            _ => Ok(value.clone()),
+        }
+    }
+
+    fn convert_property(&mut self, property_type: &str, value: &Value) -> Result<Box<dyn ZVariant>, ResourceLibError> {
+        let value = self.convert_value(property_type, value)?;
+        json_to_variant(property_type, value)
+    }
+
+    fn convert_pin_target(&mut self, target: &RefMaybeConstantValue) -> Result<(i32, Box<dyn ZVariant>), ResourceLibError> {
+        let (entity_ref, value) = match target {
+            RefMaybeConstantValue::Ref(entity_ref) => (entity_ref, None),
+            RefMaybeConstantValue::RefWithConstantValue(target) => (&target.entity_ref, Some(&target.value)),
+        };
+        let to_index = self.convert_ref(entity_ref)?.entity_index;
+        let value = match value {
+            Some(value) => self.convert_property(&value.property_type, &value.value)?,
+            None => json_to_variant("void", Value::Null)?,
+        };
+        Ok((to_index, value))
+    }
+}
+
+fn vector3(value: &Value) -> [f64; 3] {
+    ["x", "y", "z"].map(|axis| value[axis].as_f64().unwrap_or_default())
+}
+
+fn length(vector: [f64; 3]) -> f64 {
+    vector.iter().map(|component| component * component).sum::<f64>().sqrt()
+}
+
+/// Converts a matrix to QuickEntity's rotation (XYZ euler angles in degrees), position and scale representation.
+fn matrix_to_quick_entity(value: &Value) -> Value {
+    let axes = [vector3(&value["XAxis"]), vector3(&value["YAxis"]), vector3(&value["ZAxis"])];
+    let scale = axes.map(length);
+    let [x_axis, y_axis, z_axis] = [0, 1, 2].map(|i| axes[i].map(|component| if scale[i] == 0.0 { component } else { component / scale[i] }));
+
+    let (m11, m12, m13) = (x_axis[0], y_axis[0], z_axis[0]);
+    let (m22, m23) = (y_axis[1], z_axis[1]);
+    let (m32, m33) = (y_axis[2], z_axis[2]);
+
+    let rotation_y = m13.clamp(-1.0, 1.0).asin();
+    let (rotation_x, rotation_z) = if m13.abs() < 0.9999999 {
+        ((-m23).atan2(m33), (-m12).atan2(m11))
+    } else {
+        (m32.atan2(m22), 0.0)
+    };
+
+    let mut result = json!({
+        "rotation": { "x": rotation_x.to_degrees(), "y": rotation_y.to_degrees(), "z": rotation_z.to_degrees() },
+        "position": value["Trans"].clone(),
+    });
+    if scale.iter().any(|s| (s - 1.0).abs() > 1e-5) {
+        result["scale"] = json!({ "x": scale[0], "y": scale[1], "z": scale[2] });
+    }
+    result
+}
+
+fn matrix_from_quick_entity(value: &Value) -> Value {
+    let [x, y, z] = vector3(&value["rotation"]).map(f64::to_radians);
+    let scale = if value["scale"].is_object() { vector3(&value["scale"]) } else { [1.0; 3] };
+
+    let (a, b) = (x.cos(), x.sin());
+    let (c, d) = (y.cos(), y.sin());
+    let (e, f) = (z.cos(), z.sin());
+    let (ae, af, be, bf) = (a * e, a * f, b * e, b * f);
+
+    let x_axis = [c * e, af + be * d, bf - ae * d].map(|component| component * scale[0]);
+    let y_axis = [-c * f, ae - bf * d, be + af * d].map(|component| component * scale[1]);
+    let z_axis = [d, -b * c, a * c].map(|component| component * scale[2]);
+    let to_json = |axis: [f64; 3]| json!({ "x": axis[0], "y": axis[1], "z": axis[2] });
+
+    json!({
+        "XAxis": to_json(x_axis),
+        "YAxis": to_json(y_axis),
+        "ZAxis": to_json(z_axis),
+        "Trans": value["position"].clone(),
+    })
+}
+
+fn color_to_quick_entity(value: &Value) -> Value {
+    let channel = |name: &str| (value[name].as_f64().unwrap_or_default().clamp(0.0, 1.0) * 255.0).round() as u8;
+    let mut color = format!("#{:02x}{:02x}{:02x}", channel("r"), channel("g"), channel("b"));
+    if value.get("a").is_some() {
+        color.push_str(&format!("{:02x}", channel("a")));
+    }
+    Value::String(color)
+}
+
+fn color_from_quick_entity(value: &Value, with_alpha: bool) -> Result<Value, ResourceLibError> {
+    let invalid = || ResourceLibError::ConversionFailed("color_from_quick_entity");
+    let hex = value.as_str().and_then(|color| color.strip_prefix('#')).ok_or_else(invalid)?;
+    if hex.len() != if with_alpha { 8 } else { 6 } || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map(|c| c as f64 / 255.0).map_err(|_| invalid());
+    let mut color = json!({ "r": channel(0)?, "g": channel(1)?, "b": channel(2)? });
+    if with_alpha {
+        color["a"] = json!(channel(3)?);
+    }
+    Ok(color)
+}
+
+const GUID_FIELDS: [&str; 11] = ["_a", "_b", "_c", "_d", "_e", "_f", "_g", "_h", "_i", "_j", "_k"];
+
+fn guid_to_quick_entity(value: &Value) -> Value {
+    let [a, b, c, d, e, f, g, h, i, j, k] = GUID_FIELDS.map(|field| value[field].as_u64().unwrap_or_default());
+    Value::String(format!(
+        "{:08x}-{:04x}-{:04x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+        a, b, c, d, e, f, g, h, i, j, k
+    ))
+}
+
+fn guid_from_quick_entity(value: &Value) -> Result<Value, ResourceLibError> {
+    let invalid = || ResourceLibError::ConversionFailed("guid_from_quick_entity");
+    let hex: String = value.as_str().ok_or_else(invalid)?.chars().filter(|c| *c != '-').collect();
+    if hex.len() != 32 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let widths = [8, 4, 4, 2, 2, 2, 2, 2, 2, 2, 2];
+    let mut offset = 0;
+    let mut guid = serde_json::Map::new();
+    for (field, width) in GUID_FIELDS.iter().zip(widths) {
+        let part = u64::from_str_radix(&hex[offset..offset + width], 16).map_err(|_| invalid())?;
+        guid.insert(field.to_string(), json!(part));
+        offset += width;
+    }
+    Ok(Value::Object(guid))
+}
+
+fn insert_pin(
+    pins: &mut BTreeMap<String, BTreeMap<String, Vec<RefMaybeConstantValue>>>,
+    from_pin: &str,
+    to_pin: &str,
+    target: RefMaybeConstantValue,
+) {
+    pins.entry(from_pin.to_owned()).or_default().entry(to_pin.to_owned()).or_default().push(target);
+}
+
+macro_rules! impl_quick_entity {
+    ($bindings:ident) => {
+        impl QuickEntityConvert for EntityTemplate<$bindings::properties::STemplateEntityFactory, $bindings::properties::STemplateEntityBlueprint> {
+            fn to_quick_entity(&self) -> Result<Entity, ResourceLibError> {
+                use $bindings::properties::SEntityTemplateReference;
+
+                fn raw_ref(reference: &SEntityTemplateReference) -> RawRef {
+                    RawRef {
+                        entity_id: reference.entity_id,
+                        external_scene_index: reference.external_scene_index,
+                        entity_index: reference.entity_index,
+                        exposed_entity: reference.exposed_entity.to_string(),
+                    }
+                }
+
+                let factory = &self.factory;
+                let blueprint = &self.blueprint;
+                if factory.sub_entities.len() != blueprint.sub_entities.len() {
+                    return Err(ResourceLibError::ConversionFailed("to_quick_entity"));
+                }
+
+                // QuickEntity keys the entities by their ID, so duplicates would be merged.
+                let entity_ids = blueprint.sub_entities.iter().map(|sub_entity| format_entity_id(sub_entity.entity_id)).collect::<Vec<_>>();
+                if let Some(duplicate) = first_duplicate(&entity_ids) {
+                    return Err(ResourceLibError::InvalidEntityReference(format!("duplicate entity id {}", duplicate)));
+                }
+
+                let mut context = ExportContext {
+                    entity_ids,
+                    factory_meta: &self.factory_meta,
+                    external_scenes: Vec::new(),
+                };
+                context.external_scenes = factory.external_scene_type_indices_in_resource_header.iter()
+                    .map(|&index| resource_reference(&self.factory_meta, index).map(|reference| reference.hash.clone()))
+                    .collect::<Result<_, _>>()?;
+
+                let mut used_factory_references = vec![false; self.factory_meta.references.len()];
+                let mut used_blueprint_references = vec![false; self.blueprint_meta.references.len()];
+                for &index in &factory.external_scene_type_indices_in_resource_header {
+                    used_factory_references[index as usize] = true;
+                }
+                resource_reference(&self.factory_meta, factory.blueprint_index_in_resource_header)?;
+                used_factory_references[factory.blueprint_index_in_resource_header as usize] = true;
+
+                let mut entities = BTreeMap::new();
+                for (index, (factory_entity, blueprint_entity)) in factory.sub_entities.iter().zip(&blueprint.sub_entities).enumerate() {
+                    let factory_reference = resource_reference(&self.factory_meta, factory_entity.entity_type_resource_index)?;
+                    let blueprint_reference = resource_reference(&self.blueprint_meta, blueprint_entity.entity_type_resource_index)?;
+                    used_factory_references[factory_entity.entity_type_resource_index as usize] = true;
+                    used_blueprint_references[blueprint_entity.entity_type_resource_index as usize] = true;
+
+                    let mut properties = BTreeMap::new();
+                    for property in &factory_entity.property_values {
+                        properties.insert(property_name(&property.n_property_id), context.convert_property(property.value.as_ref(), false)?);
+                    }
+                    for property in &factory_entity.post_init_property_values {
+                        properties.insert(property_name(&property.n_property_id), context.convert_property(property.value.as_ref(), true)?);
+                    }
+
+                    let mut platform_specific_properties: BTreeMap<String, BTreeMap<String, Property>> = BTreeMap::new();
+                    for property in &factory_entity.platform_specific_property_values {
+                        let platform = serde_json::to_value(&property.platform)?.as_str().unwrap_or_default().to_owned();
+                        platform_specific_properties.entry(platform).or_default().insert(
+                            property_name(&property.property_value.n_property_id),
+                            context.convert_property(property.property_value.value.as_ref(), property.is_post_init)?,
+                        );
+                    }
+
+                    let mut property_aliases: BTreeMap<String, Vec<PropertyAlias>> = BTreeMap::new();
+                    for alias in &blueprint_entity.property_aliases {
+                        property_aliases.entry(alias.s_alias_name.to_string()).or_default().push(PropertyAlias {
+                            original_property: alias.s_property_name.to_string(),
+                            original_entity: Ref::Short(Some(context.local_entity(alias.entity_id)?)),
+                        });
+                    }
+
+                    let mut exposed_entities = BTreeMap::new();
+                    for exposed in &blueprint_entity.exposed_entities {
+                        exposed_entities.insert(exposed.s_name.to_string(), ExposedEntity {
+                            is_array: exposed.is_b_is_array,
+                            refers_to: exposed.a_targets.iter().map(|target| context.convert_ref(&raw_ref(target))).collect::<Result<_, _>>()?,
+                        });
+                    }
+
+                    entities.insert(context.entity_ids[index].clone(), SubEntity {
+                        parent: context.convert_ref(&raw_ref(&blueprint_entity.logical_parent))?,
+                        name: blueprint_entity.entity_name.to_string(),
+                        factory: factory_reference.hash.clone(),
+                        factory_flag: (factory_reference.flag != DEFAULT_FACTORY_FLAG).then(|| factory_reference.flag.clone()),
+                        blueprint: blueprint_reference.hash.clone(),
+                        editor_only: blueprint_entity.is_editor_only.then_some(true),
+                        properties,
+                        platform_specific_properties,
+                        events: BTreeMap::new(),
+                        input_copying: BTreeMap::new(),
+                        output_copying: BTreeMap::new(),
+                        property_aliases,
+                        exposed_entities,
+                    });
+                }
+
+                let pin_lists = [&blueprint.pin_connections, &blueprint.input_pin_forwardings, &blueprint.output_pin_forwardings];
+                for (kind, connections) in pin_lists.into_iter().enumerate() {
+                    for connection in connections {
+                        let target = context.convert_pin_target(connection.to_id, connection.constant_pin_value.as_ref())?;
+                        let entity_id = context.local_entity(connection.from_id)?;
+                        let entity = entities.get_mut(&entity_id)
+                            .ok_or_else(|| ResourceLibError::InvalidEntityReference(entity_id.clone()))?;
+                        let pins = match kind {
+                            0 => &mut entity.events,
+                            1 => &mut entity.input_copying,
+                            _ => &mut entity.output_copying,
+                        };
+                        insert_pin(pins, connection.from_pin_name.as_str(), connection.to_pin_name.as_str(), target);
+                    }
+                }
+
+                let property_overrides: Vec<PropertyOverride> = factory.property_overrides.iter()
+                    .map(|property_override| {
+                        let property = &property_override.property_value;
+                        Ok(PropertyOverride {
+                            entities: vec![context.convert_ref(&raw_ref(&property_override.property_owner))?],
+                            properties: BTreeMap::from([(property_name(&property.n_property_id), context.convert_simple_property(property.value.as_ref())?)]),
+                        })
+                    })
+                    .collect::<Result<_, ResourceLibError>>()?;
+
+                let pin_connection_overrides = blueprint.pin_connection_overrides.iter()
+                    .map(|connection| {
+                        let value = context.convert_simple_property(connection.constant_pin_value.as_ref())?;
+                        Ok(PinConnectionOverride {
+                            from_entity: context.convert_ref(&raw_ref(&connection.from_entity))?,
+                            from_pin: connection.from_pin_name.to_string(),
+                            to_entity: context.convert_ref(&raw_ref(&connection.to_entity))?,
+                            to_pin: connection.to_pin_name.to_string(),
+                            value: (value.property_type != "void").then_some(value),
+                        })
+                    })
+                    .collect::<Result<_, ResourceLibError>>()?;
+
+                let pin_connection_override_deletes = blueprint.pin_connection_override_deletes.iter()
+                    .map(|connection| {
+                        let value = context.convert_simple_property(connection.constant_pin_value.as_ref())?;
+                        Ok(PinConnectionOverrideDelete {
+                            from_entity: context.convert_ref(&raw_ref(&connection.from_entity))?,
+                            from_pin: connection.from_pin_name.to_string(),
+                            to_entity: context.convert_ref(&raw_ref(&connection.to_entity))?,
+                            to_pin: connection.to_pin_name.to_string(),
+                            value: (value.property_type != "void").then_some(value),
+                        })
+                    })
+                    .collect::<Result<_, ResourceLibError>>()?;
+
+                // Property values referencing resources mark their references as used as well.
+                let referenced_by_value = |reference: &ResourceReference| {
+                    let dependency = serde_json::to_value(Dependency::new(&reference.hash, &reference.flag, DEFAULT_RESOURCE_FLAG)).unwrap_or_default();
+                    let contains = |value: &Value| value_contains(value, &dependency);
+                    entities.values().any(|entity| {
+                        entity.properties.values().any(|property| contains(&property.value))
+                            || entity.platform_specific_properties.values().flat_map(|properties| properties.values()).any(|property| contains(&property.value))
+                    }) || property_overrides.iter().any(|property_override| {
+                        property_override.properties.values().any(|property| contains(&property.value))
+                    })
+                };
+
+                let extra_factory_dependencies = self.factory_meta.references.iter().enumerate()
+                    .filter(|(index, reference)| !used_factory_references[*index] && !referenced_by_value(reference))
+                    .map(|(_, reference)| Dependency::new(&reference.hash, &reference.flag, DEFAULT_FACTORY_FLAG))
+                    .collect();
+                let extra_blueprint_dependencies = self.blueprint_meta.references.iter().enumerate()
+                    .filter(|(index, _)| !used_blueprint_references[*index])
+                    .map(|(_, reference)| Dependency::new(&reference.hash, &reference.flag, DEFAULT_FACTORY_FLAG))
+                    .collect();
+
+                Ok(Entity {
+                    temp_hash: self.factory_meta.hash.clone(),
+                    tblu_hash: self.blueprint_meta.hash.clone(),
+                    root_entity: context.local_entity(factory.root_entity_index)?,
+                    entities,
+                    property_overrides,
+                    override_deletes: blueprint.override_deletes.iter().map(|reference| context.convert_ref(&raw_ref(reference))).collect::<Result<_, _>>()?,
+                    pin_connection_overrides,
+                    pin_connection_override_deletes,
+                    external_scenes: context.external_scenes.clone(),
+                    sub_type: SubType::from_raw(factory.sub_type),
+                    quick_entity_version: QUICK_ENTITY_VERSION,
+                    extra_factory_dependencies,
+                    extra_blueprint_dependencies,
+                    comments: Vec::new(),
+                })
+            }
+
+            fn from_quick_entity(entity: &Entity) -> Result<Self, ResourceLibError> {
+                use $bindings::properties::{
+                    SEntityTemplatePinConnection, SEntityTemplatePlatformSpecificProperty, SEntityTemplateProperty,
+                    SEntityTemplatePropertyAlias, SEntityTemplatePropertyOverride, SEntityTemplateExposedEntity,
+                    SEntityTemplateReference, SExternalEntityTemplatePinConnection, STemplateBlueprintSubEntity,
+                    STemplateEntityBlueprint, STemplateEntityFactory, STemplateFactorySubEntity,
+                };
+
+                fn template_ref(raw: RawRef) -> SEntityTemplateReference {
+                    SEntityTemplateReference {
+                        entity_id: raw.entity_id,
+                        external_scene_index: raw.external_scene_index,
+                        entity_index: raw.entity_index,
+                        exposed_entity: raw.exposed_entity.into(),
+                    }
+                }
+
+                fn template_property(name: &str, value: Box<dyn ZVariant>) -> SEntityTemplateProperty {
+                    SEntityTemplateProperty { n_property_id: property_id(name), value }
+                }
+
+                let mut context = ImportContext::new(entity);
+
+                // Factories and blueprints come first so the entity type indices stay stable.
+                for sub_entity in entity.entities.values() {
+                    context.factory_reference(&sub_entity.factory, sub_entity.factory_flag.as_deref().unwrap_or(DEFAULT_FACTORY_FLAG));
+                    context.blueprint_reference(&sub_entity.blueprint, DEFAULT_FACTORY_FLAG);
+                }
+                let external_scene_type_indices = entity.external_scenes.iter()
+                    .map(|scene| context.factory_reference(scene, DEFAULT_FACTORY_FLAG))
+                    .collect::<Vec<_>>();
+                context.external_scene_type_indices = external_scene_type_indices.clone();
+
+                let mut factory_entities = Vec::with_capacity(entity.entities.len());
+                let mut blueprint_entities = Vec::with_capacity(entity.entities.len());
+                let mut pin_connections = Vec::new();
+                let mut input_pin_forwardings = Vec::new();
+                let mut output_pin_forwardings = Vec::new();
+
+                for (entity_id, sub_entity) in &entity.entities {
+                    let from_id = context.local_index(entity_id)?;
+
+                    let mut property_values = Vec::new();
+                    let mut post_init_property_values = Vec::new();
+                    for (name, property) in &sub_entity.properties {
+                        let value = template_property(name, context.convert_property(&property.property_type, &property.value)?);
+                        if property.post_init.unwrap_or(false) {
+                            post_init_property_values.push(value);
+                        } else {
+                            property_values.push(value);
+                        }
+                    }
+
+                    let mut platform_specific_property_values = Vec::new();
+                    for (platform, properties) in &sub_entity.platform_specific_properties {
+                        for (name, property) in properties {
+                            platform_specific_property_values.push(SEntityTemplatePlatformSpecificProperty {
+                                property_value: template_property(name, context.convert_property(&property.property_type, &property.value)?),
+                                platform: serde_json::from_value(Value::String(platform.clone()))?,
+                                is_post_init: property.post_init.unwrap_or(false),
+                            });
+                        }
+                    }
+
+                    let pin_lists = [
+                        (&sub_entity.events, &mut pin_connections),
+                        (&sub_entity.input_copying, &mut input_pin_forwardings),
+                        (&sub_entity.output_copying, &mut output_pin_forwardings),
+                    ];
+                    for (pins, connections) in pin_lists {
+                        for (from_pin, targets) in pins {
+                            for (to_pin, targets) in targets {
+                                for target in targets {
+                                    let (to_id, constant_pin_value) = context.convert_pin_target(target)?;
+                                    connections.push(SEntityTemplatePinConnection {
+                                        from_id,
+                                        to_id,
+                                        from_pin_name: from_pin.as_str().into(),
+                                        to_pin_name: to_pin.as_str().into(),
+                                        constant_pin_value,
+                                    });
+                                }
+                            }
+                        }
+                    }
+
+                    let mut property_aliases = Vec::new();
+                    for (alias_name, aliases) in &sub_entity.property_aliases {
+                        for alias in aliases {
+                            property_aliases.push(SEntityTemplatePropertyAlias {
+                                s_alias_name: alias_name.as_str().into(),
+                                entity_id: context.convert_ref(&alias.original_entity)?.entity_index,
+                                s_property_name: alias.original_property.as_str().into(),
+                            });
+                        }
+                    }
+
+                    let mut exposed_entities = Vec::new();
+                    for (name, exposed) in &sub_entity.exposed_entities {
+                        exposed_entities.push(SEntityTemplateExposedEntity {
+                            s_name: name.as_str().into(),
+                            is_b_is_array: exposed.is_array,
+                            a_targets: exposed.refers_to.iter().map(|target| context.convert_ref(target).map(template_ref)).collect::<Result<_, _>>()?,
+                        });
+                    }
+
+                    let factory_flag = sub_entity.factory_flag.as_deref().unwrap_or(DEFAULT_FACTORY_FLAG);
+                    factory_entities.push(STemplateFactorySubEntity {
+                        logical_parent: template_ref(context.convert_ref(&sub_entity.parent)?),
+                        entity_type_resource_index: context.factory_reference(&sub_entity.factory, factory_flag),
+                        property_values,
+                        post_init_property_values,
+                        platform_specific_property_values,
+                    });
+                    blueprint_entities.push(STemplateBlueprintSubEntity {
+                        logical_parent: template_ref(context.convert_ref(&sub_entity.parent)?),
+                        entity_type_resource_index: context.blueprint_reference(&sub_entity.blueprint, DEFAULT_FACTORY_FLAG),
+                        entity_id: parse_entity_id(entity_id)?,
+                        is_editor_only: sub_entity.editor_only.unwrap_or(false),
+                        entity_name: sub_entity.name.as_str().into(),
+                        property_aliases,
+                        exposed_entities,
+                        // Not represented in QuickEntity's format yet.
+                        exposed_interfaces: Vec::new(),
+                        entity_subsets: Vec::new(),
+                    });
+                }
+
+                let mut property_overrides = Vec::new();
+                for property_override in &entity.property_overrides {
+                    for owner in &property_override.entities {
+                        for (name, property) in &property_override.properties {
+                            property_overrides.push(SEntityTemplatePropertyOverride {
+                                property_owner: template_ref(context.convert_ref(owner)?),
+                                property_value: template_property(name, context.convert_property(&property.property_type, &property.value)?),
+                            });
+                        }
+                    }
+                }
+
+                let mut convert_external_connection = |from_entity: &Ref, from_pin: &str, to_entity: &Ref, to_pin: &str, value: Option<&SimpleProperty>| {
+                    let constant_pin_value = match value {
+                        Some(value) => context.convert_property(&value.property_type, &value.value)?,
+                        None => json_to_variant("void", Value::Null)?,
+                    };
+                    Ok::<_, ResourceLibError>(SExternalEntityTemplatePinConnection {
+                        from_entity: template_ref(context.convert_ref(from_entity)?),
+                        to_entity: template_ref(context.convert_ref(to_entity)?),
+                        from_pin_name: from_pin.into(),
+                        to_pin_name: to_pin.into(),
+                        constant_pin_value,
+                    })
+                };
+                let pin_connection_overrides = entity.pin_connection_overrides.iter()
+                    .map(|connection| convert_external_connection(&connection.from_entity, &connection.from_pin, &connection.to_entity, &connection.to_pin, connection.value.as_ref()))
+                    .collect::<Result<_, _>>()?;
+                let pin_connection_override_deletes = entity.pin_connection_override_deletes.iter()
+                    .map(|connection| convert_external_connection(&connection.from_entity, &connection.from_pin, &connection.to_entity, &connection.to_pin, connection.value.as_ref()))
+                    .collect::<Result<_, _>>()?;
+
+                let override_deletes = entity.override_deletes.iter()
+                    .map(|reference| context.convert_ref(reference).map(template_ref))
+                    .collect::<Result<_, _>>()?;
+
+                for dependency in &entity.extra_factory_dependencies {
+                    let (hash, flag) = dependency.hash_and_flag(DEFAULT_FACTORY_FLAG);
+                    context.factory_reference(hash, flag);
+                }
+                for dependency in &entity.extra_blueprint_dependencies {
+                    let (hash, flag) = dependency.hash_and_flag(DEFAULT_FACTORY_FLAG);
+                    context.blueprint_reference(hash, flag);
+                }
+
+                let root_entity_index = context.local_index(&entity.root_entity)?;
+                let sub_type = entity.sub_type.to_raw();
+                // The factory references its own blueprint as well.
+                let blueprint_index_in_resource_header = context.factory_reference(&entity.tblu_hash, DEFAULT_FACTORY_FLAG);
+
+                Ok(EntityTemplate {
+                    factory: STemplateEntityFactory {
+                        sub_type,
+                        blueprint_index_in_resource_header,
+                        root_entity_index,
+                        sub_entities: factory_entities,
+                        property_overrides,
+                        external_scene_type_indices_in_resource_header: external_scene_type_indices.clone(),
+                    },
+                    factory_meta: ResourceMeta { hash: entity.temp_hash.clone(), references: context.factory_references },
+                    blueprint: STemplateEntityBlueprint {
+                        sub_type,
+                        root_entity_index,
+                        sub_entities: blueprint_entities,
+                        external_scene_type_indices_in_resource_header: external_scene_type_indices,
+                        pin_connections,
+                        input_pin_forwardings,
+                        output_pin_forwardings,
+                        override_deletes,
+                        pin_connection_overrides,
+                        pin_connection_override_deletes,
+                    },
+                    blueprint_meta: ResourceMeta { hash: entity.tblu_hash.clone(), references: context.blueprint_references },
+                })
+            }
+        }
+    };
+}
+
+#[cfg(feature = "hm2")]
+impl_quick_entity!(hm2_bindings);
+#[cfg(feature = "hm3")]
+impl_quick_entity!(hm3_bindings);
+
+fn value_contains(value: &Value, needle: &Value) -> bool {
+    value == needle
+        || match value {
+            Value::Array(items) => items.iter().any(|item| value_contains(item, needle)),
+            Value::Object(map) => map.values().any(|item| value_contains(item, needle)),
+            _ => false,
+        }
+}
+
+fn diff_list<T: PartialEq + Clone>(
+    original: &[T],
+    modified: &[T],
+    remove: impl Fn(T) -> PatchOperation,
+    add: impl Fn(T) -> PatchOperation,
+    patch: &mut Vec<PatchOperation>,
+) {
+    patch.extend(original.iter().filter(|item| !modified.contains(*item)).cloned().map(remove));
+    patch.extend(modified.iter().filter(|item| !original.contains(*item)).cloned().map(add));
+}
+
+type PinMap = BTreeMap<String, BTreeMap<String, Vec<RefMaybeConstantValue>>>;
+
+fn pin_entries(pins: &PinMap) -> Vec<(&String, &String, &RefMaybeConstantValue)> {
+    pins.iter()
+        .flat_map(|(from_pin, targets)| targets.iter().flat_map(move |(to_pin, refs)| refs.iter().map(move |target| (from_pin, to_pin, target))))
+        .collect()
+}
+
+fn diff_pins(
+    original: &PinMap,
+    modified: &PinMap,
+    remove: fn(String, String, RefMaybeConstantValue) -> SubEntityOperation,
+    add: fn(String, String, RefMaybeConstantValue) -> SubEntityOperation,
+    operations: &mut Vec<SubEntityOperation>,
+) {
+    let original = pin_entries(original);
+    let modified = pin_entries(modified);
+    for &(from_pin, to_pin, target) in original.iter().filter(|entry| !modified.contains(*entry)) {
+        operations.push(remove(from_pin.clone(), to_pin.clone(), target.clone()));
+    }
+    for &(from_pin, to_pin, target) in modified.iter().filter(|entry| !original.contains(*entry)) {
+        operations.push(add(from_pin.clone(), to_pin.clone(), target.clone()));
+    }
+}
+
+fn diff_sub_entity(original: &SubEntity, modified: &SubEntity) -> Vec<SubEntityOperation> {
+    let mut operations = Vec::new();
+
+    if original.parent != modified.parent {
+        operations.push(SubEntityOperation::SetParent(modified.parent.clone()));
+    }
+    if original.name != modified.name {
+        operations.push(SubEntityOperation::SetName(modified.name.clone()));
+    }
+    if original.factory != modified.factory {
+        operations.push(SubEntityOperation::SetFactory(modified.factory.clone()));
+    }
+    if original.factory_flag != modified.factory_flag {
+        operations.push(SubEntityOperation::SetFactoryFlag(modified.factory_flag.clone()));
+    }
+    if original.blueprint != modified.blueprint {
+        operations.push(SubEntityOperation::SetBlueprint(modified.blueprint.clone()));
+    }
+    if original.editor_only != modified.editor_only {
+        operations.push(SubEntityOperation::SetEditorOnly(modified.editor_only));
+    }
+
+    for name in original.properties.keys().filter(|name| !modified.properties.contains_key(*name)) {
+        operations.push(SubEntityOperation::RemovePropertyByName(name.clone()));
+    }
+    for (name, property) in &modified.properties {
+        let Some(original_property) = original.properties.get(name) else {
+            operations.push(SubEntityOperation::AddProperty(name.clone(), property.clone()));
+            continue;
+        };
+        if original_property.property_type != property.property_type {
+            operations.push(SubEntityOperation::SetPropertyType(name.clone(), property.property_type.clone()));
+        }
+        if original_property.value != property.value {
+            operations.push(SubEntityOperation::SetPropertyValue { property_name: name.clone(), value: property.value.clone() });
+        }
+        if original_property.post_init.unwrap_or(false) != property.post_init.unwrap_or(false) {
+            operations.push(SubEntityOperation::SetPropertyPostInit(name.clone(), property.post_init.unwrap_or(false)));
+        }
+    }
+
+    for (platform, properties) in &original.platform_specific_properties {
+        for (name, property) in properties {
+            if modified.platform_specific_properties.get(platform).and_then(|properties| properties.get(name)) != Some(property) {
+                operations.push(SubEntityOperation::RemovePlatformSpecificPropertyByName(platform.clone(), name.clone()));
+            }
+        }
+    }
+    for (platform, properties) in &modified.platform_specific_properties {
+        for (name, property) in properties {
+            if original.platform_specific_properties.get(platform).and_then(|properties| properties.get(name)) != Some(property) {
+                operations.push(SubEntityOperation::AddPlatformSpecificProperty(platform.clone(), name.clone(), property.clone()));
+            }
+        }
+    }
+
+    diff_pins(&original.events, &modified.events, SubEntityOperation::RemoveEventConnection, SubEntityOperation::AddEventConnection, &mut operations);
+    diff_pins(&original.input_copying, &modified.input_copying, SubEntityOperation::RemoveInputCopyingConnection, SubEntityOperation::AddInputCopyingConnection, &mut operations);
+    diff_pins(&original.output_copying, &modified.output_copying, SubEntityOperation::RemoveOutputCopyingConnection, SubEntityOperation::AddOutputCopyingConnection, &mut operations);
+
+    for (name, aliases) in &original.property_aliases {
+        let modified_aliases = modified.property_aliases.get(name);
+        for alias in aliases.iter().filter(|alias| !modified_aliases.is_some_and(|aliases| aliases.contains(alias))) {
+            operations.push(SubEntityOperation::RemoveConnectionForPropertyAlias(name.clone(), alias.clone()));
+        }
+    }
+    for (name, aliases) in &modified.property_aliases {
+        let original_aliases = original.property_aliases.get(name);
+        for alias in aliases.iter().filter(|alias| !original_aliases.is_some_and(|aliases| aliases.contains(alias))) {
+            operations.push(SubEntityOperation::AddPropertyAliasConnection(name.clone(), alias.clone()));
+        }
+    }
+
+    for name in original.exposed_entities.keys().filter(|name| !modified.exposed_entities.contains_key(*name)) {
+        operations.push(SubEntityOperation::RemoveExposedEntity(name.clone()));
+    }
+    for (name, exposed) in &modified.exposed_entities {
+        if original.exposed_entities.get(name) != Some(exposed) {
+            operations.push(SubEntityOperation::SetExposedEntity(name.clone(), exposed.clone()));
+        }
+    }
+
+    operations
+}
+
+/// Generates a patch that turns `original` into `modified` when applied with [`apply_patch`].
+pub fn generate_patch(original: &Entity, modified: &Entity) -> Patch {
+    let mut patch = Vec::new();
+
+    if original.root_entity != modified.root_entity {
+        patch.push(PatchOperation::SetRootEntity(modified.root_entity.clone()));
+    }
+    if original.sub_type != modified.sub_type {
+        patch.push(PatchOperation::SetSubType(modified.sub_type));
+    }
+
+    for entity_id in original.entities.keys().filter(|id| !modified.entities.contains_key(*id)) {
+        patch.push(PatchOperation::RemoveEntityByID(entity_id.clone()));
+    }
+    for (entity_id, sub_entity) in &modified.entities {
+        match original.entities.get(entity_id) {
+            None => patch.push(PatchOperation::AddEntity(entity_id.clone(), Box::new(sub_entity.clone()))),
+            Some(original_entity) => patch.extend(
+                diff_sub_entity(original_entity, sub_entity)
+                    .into_iter()
+                    .map(|operation| PatchOperation::SubEntityOperation(entity_id.clone(), operation)),
+            ),
+        }
+    }
+
+    diff_list(&original.property_overrides, &modified.property_overrides, PatchOperation::RemovePropertyOverride, PatchOperation::AddPropertyOverride, &mut patch);
+    diff_list(&original.override_deletes, &modified.override_deletes, PatchOperation::RemoveOverrideDelete, PatchOperation::AddOverrideDelete, &mut patch);
+    diff_list(&original.pin_connection_overrides, &modified.pin_connection_overrides, PatchOperation::RemovePinConnectionOverride, PatchOperation::AddPinConnectionOverride, &mut patch);
+    diff_list(&original.pin_connection_override_deletes, &modified.pin_connection_override_deletes, PatchOperation::RemovePinConnectionOverrideDelete, PatchOperation::AddPinConnectionOverrideDelete, &mut patch);
+    diff_list(&original.external_scenes, &modified.external_scenes, PatchOperation::RemoveExternalScene, PatchOperation::AddExternalScene, &mut patch);
+    diff_list(&original.extra_factory_dependencies, &modified.extra_factory_dependencies, PatchOperation::RemoveExtraFactoryDependency, PatchOperation::AddExtraFactoryDependency, &mut patch);
+    diff_list(&original.extra_blueprint_dependencies, &modified.extra_blueprint_dependencies, PatchOperation::RemoveExtraBlueprintDependency, PatchOperation::AddExtraBlueprintDependency, &mut patch);
+
+    Patch {
+        temp_hash: modified.temp_hash.clone(),
+        tblu_hash: modified.tblu_hash.clone(),
+        patch,
+        patch_version: PATCH_VERSION,
+    }
+}
+
+fn remove_item<T: PartialEq>(items: &mut Vec<T>, item: &T, description: &str) -> Result<(), ResourceLibError> {
+    let index = items.iter().position(|existing| existing == item)
+        .ok_or_else(|| ResourceLibError::PatchConflict(format!("{} does not exist", description)))?;
+    items.remove(index);
+    Ok(())
+}
+
+fn remove_pin(pins: &mut PinMap, from_pin: &str, to_pin: &str, target: &RefMaybeConstantValue) -> Result<(), ResourceLibError> {
+    let targets = pins.get_mut(from_pin).and_then(|targets| targets.get_mut(to_pin))
+        .ok_or_else(|| ResourceLibError::PatchConflict(format!("pin connection {} -> {} does not exist", from_pin, to_pin)))?;
+    remove_item(targets, target, "pin connection")?;
+
+    let from_targets = pins.get_mut(from_pin).expect("checked above");
+    if from_targets.get(to_pin).is_some_and(Vec::is_empty) {
+        from_targets.remove(to_pin);
+    }
+    if from_targets.is_empty() {
+        pins.remove(from_pin);
+    }
+    Ok(())
+}
+
+fn apply_sub_entity_operation(sub_entity: &mut SubEntity, operation: &SubEntityOperation) -> Result<(), ResourceLibError> {
+    let missing_property = |name: &str| ResourceLibError::PatchConflict(format!("property {} does not exist", name));
+
+    match operation {
+        SubEntityOperation::SetParent(parent) => sub_entity.parent = parent.clone(),
+        SubEntityOperation::SetName(name) => sub_entity.name = name.clone(),
+        SubEntityOperation::SetFactory(factory) => sub_entity.factory = factory.clone(),
+        SubEntityOperation::SetFactoryFlag(flag) => sub_entity.factory_flag = flag.clone(),
+        SubEntityOperation::SetBlueprint(blueprint) => sub_entity.blueprint = blueprint.clone(),
+        SubEntityOperation::SetEditorOnly(editor_only) => sub_entity.editor_only = *editor_only,
+        SubEntityOperation::AddProperty(name, property) => {
+            sub_entity.properties.insert(name.clone(), property.clone());
+        }
+        SubEntityOperation::RemovePropertyByName(name) => {
+            sub_entity.properties.remove(name).ok_or_else(|| missing_property(name))?;
+        }
+        SubEntityOperation::SetPropertyType(name, property_type) => {
+            sub_entity.properties.get_mut(name).ok_or_else(|| missing_property(name))?.property_type = property_type.clone();
+        }
+        SubEntityOperation::SetPropertyValue { property_name, value } => {
+            sub_entity.properties.get_mut(property_name).ok_or_else(|| missing_property(property_name))?.value = value.clone();
+        }
+        SubEntityOperation::SetPropertyPostInit(name, post_init) => {
+            sub_entity.properties.get_mut(name).ok_or_else(|| missing_property(name))?.post_init = post_init.then_some(true);
+        }
+        SubEntityOperation::AddPlatformSpecificProperty(platform, name, property) => {
+            sub_entity.platform_specific_properties.entry(platform.clone()).or_default().insert(name.clone(), property.clone());
+        }
+        SubEntityOperation::RemovePlatformSpecificPropertyByName(platform, name) => {
+            let properties = sub_entity.platform_specific_properties.get_mut(platform).ok_or_else(|| missing_property(name))?;
+            properties.remove(name).ok_or_else(|| missing_property(name))?;
+            if properties.is_empty() {
+                sub_entity.platform_specific_properties.remove(platform);
+            }
+        }
+        SubEntityOperation::AddEventConnection(from_pin, to_pin, target) => insert_pin(&mut sub_entity.events, from_pin, to_pin, target.clone()),
+        SubEntityOperation::RemoveEventConnection(from_pin, to_pin, target) => remove_pin(&mut sub_entity.events, from_pin, to_pin, target)?,
+        SubEntityOperation::AddInputCopyingConnection(from_pin, to_pin, target) => insert_pin(&mut sub_entity.input_copying, from_pin, to_pin, target.clone()),
+        SubEntityOperation::RemoveInputCopyingConnection(from_pin, to_pin, target) => remove_pin(&mut sub_entity.input_copying, from_pin, to_pin, target)?,
+        SubEntityOperation::AddOutputCopyingConnection(from_pin, to_pin, target) => insert_pin(&mut sub_entity.output_copying, from_pin, to_pin, target.clone()),
+        SubEntityOperation::RemoveOutputCopyingConnection(from_pin, to_pin, target) => remove_pin(&mut sub_entity.output_copying, from_pin, to_pin, target)?,
+        SubEntityOperation::AddPropertyAliasConnection(name, alias) => {
+            sub_entity.property_aliases.entry(name.clone()).or_default().push(alias.clone());
+        }
+        SubEntityOperation::RemoveConnectionForPropertyAlias(name, alias) => {
+            let aliases = sub_entity.property_aliases.get_mut(name)
+                .ok_or_else(|| ResourceLibError::PatchConflict(format!("property alias {} does not exist", name)))?;
+            remove_item(aliases, alias, "property alias connection")?;
+            if aliases.is_empty() {
+                sub_entity.property_aliases.remove(name);
+            }
+        }
+        SubEntityOperation::SetExposedEntity(name, exposed) => {
+            sub_entity.exposed_entities.insert(name.clone(), exposed.clone());
+        }
+        SubEntityOperation::RemoveExposedEntity(name) => {
+            sub_entity.exposed_entities.remove(name)
+                .ok_or_else(|| ResourceLibError::PatchConflict(format!("exposed entity {} does not exist", name)))?;
+        }
+    }
+    Ok(())
+}
+
+/// Applies the operations of a patch to an entity document, in order.
+pub fn apply_patch(entity: &mut Entity, patch: &Patch) -> Result<(), ResourceLibError> {
+    let missing_entity = |entity_id: &str| ResourceLibError::PatchConflict(format!("entity {} does not exist", entity_id));
+
+    for operation in &patch.patch {
+        match operation {
+            PatchOperation::SetRootEntity(entity_id) => entity.root_entity = entity_id.clone(),
+            PatchOperation::SetSubType(sub_type) => entity.sub_type = *sub_type,
+            PatchOperation::AddEntity(entity_id, sub_entity) => {
+                if entity.entities.insert(entity_id.clone(), (**sub_entity).clone()).is_some() {
+                    return Err(ResourceLibError::PatchConflict(format!("entity {} already exists", entity_id)));
+                }
+            }
+            PatchOperation::RemoveEntityByID(entity_id) => {
+                entity.entities.remove(entity_id).ok_or_else(|| missing_entity(entity_id))?;
+            }
+            PatchOperation::SubEntityOperation(entity_id, operation) => {
+                let sub_entity = entity.entities.get_mut(entity_id).ok_or_else(|| missing_entity(entity_id))?;
+                apply_sub_entity_operation(sub_entity, operation)?;
+            }
+            PatchOperation::AddPropertyOverride(property_override) => entity.property_overrides.push(property_override.clone()),
+            PatchOperation::RemovePropertyOverride(property_override) => remove_item(&mut entity.property_overrides, property_override, "property override")?,
+            PatchOperation::AddOverrideDelete(reference) => entity.override_deletes.push(reference.clone()),
+            PatchOperation::RemoveOverrideDelete(reference) => remove_item(&mut entity.override_deletes, reference, "override delete")?,
+            PatchOperation::AddPinConnectionOverride(connection) => entity.pin_connection_overrides.push(connection.clone()),
+            PatchOperation::RemovePinConnectionOverride(connection) => remove_item(&mut entity.pin_connection_overrides, connection, "pin connection override")?,
+            PatchOperation::AddPinConnectionOverrideDelete(connection) => entity.pin_connection_override_deletes.push(connection.clone()),
+            PatchOperation::RemovePinConnectionOverrideDelete(connection) => remove_item(&mut entity.pin_connection_override_deletes, connection, "pin connection override delete")?,
+            PatchOperation::AddExternalScene(scene) => entity.external_scenes.push(scene.clone()),
+            PatchOperation::RemoveExternalScene(scene) => remove_item(&mut entity.external_scenes, scene, "external scene")?,
+            PatchOperation::AddExtraFactoryDependency(dependency) => entity.extra_factory_dependencies.push(dependency.clone()),
+            PatchOperation::RemoveExtraFactoryDependency(dependency) => remove_item(&mut entity.extra_factory_dependencies, dependency, "extra factory dependency")?,
+            PatchOperation::AddExtraBlueprintDependency(dependency) => entity.extra_blueprint_dependencies.push(dependency.clone()),
+            PatchOperation::RemoveExtraBlueprintDependency(dependency) => remove_item(&mut entity.extra_blueprint_dependencies, dependency, "extra blueprint dependency")?,
+        }
+    }
+
+    entity.temp_hash = patch.temp_hash.clone();
+    entity.tblu_hash = patch.tblu_hash.clone();
+    Ok(())
+}
@@ -0,0 +1,152 @@
+//! A pure-Rust implementation of the converters and generators of resource types with plain layouts, used by
+//! [`ResourceConverter::rust`](crate::ResourceConverter::rust) and
+//! [`ResourceGenerator::rust`](crate::ResourceGenerator::rust).
+//!
+//! A resource is a BIN1 container:
+//!
+//! - a 16 byte header: the magic `BIN1`, a zero byte, the alignment of the data, the number of segments, a zero byte,
+//!   the size of the data as a big-endian `uint32` and four zero bytes;
+//! - the data: the root struct followed by the elements of its arrays and strings, in the [`Format::Resource`] layout
+//!   with pointers relative to the start of the data;
+//! - the segments: a `uint32` type, a `uint32` size and the offsets of the relocated pointers or runtime resource IDs
+//!   in the data, as a `uint32` count followed by that many `uint32`s. Empty segments are left out.
+//!
+//! `tests/backend_tests.rs` compares this implementation to ResourceLib.
+//!
+//! [`Format::Resource`]: glacier_types::binary::Format::Resource
+
+#[cfg(feature = "hm2016")]
+use glacier_hm2016 as hm2016_bindings;
+#[cfg(feature = "hm2")]
+use glacier_hm2 as hm2_bindings;
+#[cfg(feature = "hm3")]
+use glacier_hm3 as hm3_bindings;
+use glacier_types::binary::{BinaryReader, BinaryWriter, GameStruct};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use crate::{ResourceLibError, WoaVersion};
+
+const MAGIC: &[u8; 4] = b"BIN1";
+const HEADER_SIZE: usize = 16;
+const RELOCATION_SEGMENT: u32 = 0x12EBA5ED;
+const RUNTIME_RESOURCE_ID_SEGMENT: u32 = 0x578FBCEE;
+
+/// The converter and generator of a resource type.
+#[derive(Debug, Clone)]
+pub(crate) struct RustResource {
+    convert: fn(&[u8]) -> Result<String, ResourceLibError>,
+    generate: fn(&str) -> Result<Vec<u8>, ResourceLibError>,
+}
+
+impl RustResource {
+    pub(crate) fn new(version: WoaVersion, resource_type: &str) -> Result<Self, ResourceLibError> {
+        resource(version, resource_type).ok_or_else(|| ResourceLibError::InvalidResourceType(resource_type.to_string()))
+    }
+
+    pub(crate) fn convert(&self, resource_data: &[u8]) -> Result<String, ResourceLibError> {
+        (self.convert)(resource_data)
+    }
+
+    pub(crate) fn generate(&self, json: &str) -> Result<Vec<u8>, ResourceLibError> {
+        (self.generate)(json)
+    }
+}
+
+/// Declares the resource types implemented in Rust, by version: the feature of the resource type, its name and the
+/// generated root struct.
+macro_rules! rust_resources {
+    ($($version:ident, $version_feature:literal, $bindings:ident: [$($feature:literal, $resource_type:literal, $root:ident;)*])*) => {
+        fn resource(version: WoaVersion, resource_type: &str) -> Option<RustResource> {
+            match (version, resource_type) {
+                $($(
+                #[cfg(all(feature = $version_feature, feature = $feature))]
+                (WoaVersion::$version, $resource_type) => Some(RustResource {
+                    convert: resource_to_json::<$bindings::properties::$root>,
+                    generate: json_to_resource::<$bindings::properties::$root>,
+                }),
+                )*)*
+                _ => None,
+            }
+        }
+
+        /// The resource types [`ResourceConverter::rust`](crate::ResourceConverter::rust) and
+        /// [`ResourceGenerator::rust`](crate::ResourceGenerator::rust) support for a version, with the enabled features.
+        #[allow(unused_variables)]
+        pub fn supported_resource_types(version: WoaVersion) -> Vec<&'static str> {
+            let mut resource_types = Vec::new();
+            $($(
+            #[cfg(all(feature = $version_feature, feature = $feature))]
+            if matches!(version, WoaVersion::$version) {
+                resource_types.push($resource_type);
+            }
+            )*)*
+            resource_types
+        }
+    };
+}
+
+rust_resources! {
+    HM2016, "hm2016", hm2016_bindings: [
+        "vidb", "VIDB", SVideoDatabaseData;
+        "crmd", "CRMD", SCrowdMapData;
+        "gidx", "GIDX", SResourceIndex;
+    ]
+    HM2, "hm2", hm2_bindings: [
+        "vidb", "VIDB", SVideoDatabaseData;
+        "crmd", "CRMD", SCrowdMapData;
+        "gidx", "GIDX", SResourceIndex;
+    ]
+    HM3, "hm3", hm3_bindings: [
+        "vidb", "VIDB", SVideoDatabaseData;
+        "crmd", "CRMD", SCrowdMapData;
+        "gidx", "GIDX", SResourceIndex;
+    ]
+}
+
+/// Reads the root struct of a resource.
+pub fn read_resource<T: GameStruct>(resource_data: &[u8]) -> Result<T, ResourceLibError> {
+    if resource_data.len() < HEADER_SIZE || &resource_data[..4] != MAGIC {
+        return Err(ResourceLibError::InvalidResource("missing BIN1 header".to_string()));
+    }
+    let data_size = u32::from_be_bytes(resource_data[8..12].try_into().unwrap()) as usize;
+    let data = resource_data.get(HEADER_SIZE..HEADER_SIZE + data_size)
+        .ok_or_else(|| ResourceLibError::InvalidResource(format!("the data of {} bytes is truncated", data_size)))?;
+    Ok(BinaryReader::resource(data).read(0)?)
+}
+
+/// Writes a struct as the root of a resource.
+pub fn write_resource<T: GameStruct>(root: &T) -> Vec<u8> {
+    let mut writer = BinaryWriter::resource();
+    writer.append(root);
+
+    let segments = [
+        (RELOCATION_SEGMENT, writer.relocations()),
+        (RUNTIME_RESOURCE_ID_SEGMENT, writer.runtime_resource_ids()),
+    ];
+    let segments = segments.iter().filter(|(_, offsets)| !offsets.is_empty()).collect::<Vec<_>>();
+
+    let data = writer.data();
+    let mut resource_data = Vec::with_capacity(HEADER_SIZE + data.len());
+    resource_data.extend_from_slice(MAGIC);
+    resource_data.extend_from_slice(&[0, writer.alignment() as u8, segments.len() as u8, 0]);
+    resource_data.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    resource_data.extend_from_slice(&[0; 4]);
+    resource_data.extend_from_slice(data);
+    for (segment_type, offsets) in segments {
+        resource_data.extend_from_slice(&segment_type.to_le_bytes());
+        resource_data.extend_from_slice(&((offsets.len() + 1) as u32 * 4).to_le_bytes());
+        resource_data.extend_from_slice(&(offsets.len() as u32).to_le_bytes());
+        for &offset in offsets.iter() {
+            resource_data.extend_from_slice(&(offset as u32).to_le_bytes());
+        }
+    }
+    resource_data
+}
+
+fn resource_to_json<T: GameStruct + Serialize>(resource_data: &[u8]) -> Result<String, ResourceLibError> {
+    Ok(serde_json::to_string(&read_resource::<T>(resource_data)?)?)
+}
+
+fn json_to_resource<T: GameStruct + DeserializeOwned>(json: &str) -> Result<Vec<u8>, ResourceLibError> {
+    Ok(write_resource(&serde_json::from_str::<T>(json)?))
+}
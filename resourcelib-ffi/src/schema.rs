@@ -0,0 +1,80 @@
+//! JSON Schemas of the JSON ResourceLib reads and writes, for validating resources in editors.
+//!
+//! glacier-codegen generates a schema with a definition for every class and enum of a version from its ZHMGen.h.
+//! `ZVariant`s are checked through their `$type` and `$val` tags.
+
+use std::collections::HashSet;
+#[cfg(feature = "hm2016")]
+use glacier_hm2016 as hm2016_bindings;
+#[cfg(feature = "hm2")]
+use glacier_hm2 as hm2_bindings;
+#[cfg(feature = "hm3")]
+use glacier_hm3 as hm3_bindings;
+use serde_json::{Map, Value};
+use crate::WoaVersion;
+
+/// The generated schema of a version and its resource types with their root classes.
+fn bindings(version: WoaVersion) -> Option<(&'static str, &'static [(&'static str, &'static str)])> {
+    match version {
+        #[cfg(feature = "hm2016")]
+        WoaVersion::HM2016 => Some((hm2016_bindings::JSON_SCHEMA, hm2016_bindings::RESOURCE_ROOTS)),
+        #[cfg(feature = "hm2")]
+        WoaVersion::HM2 => Some((hm2_bindings::JSON_SCHEMA, hm2_bindings::RESOURCE_ROOTS)),
+        #[cfg(feature = "hm3")]
+        WoaVersion::HM3 => Some((hm3_bindings::JSON_SCHEMA, hm3_bindings::RESOURCE_ROOTS)),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
+/// The resource types a schema can be generated for, with the enabled features.
+pub fn resource_types(version: WoaVersion) -> Vec<&'static str> {
+    bindings(version)
+        .map(|(_, roots)| roots.iter().map(|(resource_type, _)| *resource_type).collect())
+        .unwrap_or_default()
+}
+
+/// The JSON Schema of a resource type, e.g. `schema_for(WoaVersion::HM3, "TEMP")`. It only contains the definitions
+/// the root class needs.
+///
+/// Returns `None` if the feature of the version or of the resource type is disabled.
+pub fn schema_for(version: WoaVersion, resource_type: &str) -> Option<Value> {
+    let (schema, roots) = bindings(version)?;
+    let (_, root) = roots.iter().find(|(name, _)| name.eq_ignore_ascii_case(resource_type))?;
+    let mut schema: Map<String, Value> = serde_json::from_str(schema).expect("invalid JSON schema");
+    let Some(Value::Object(definitions)) = schema.remove("$defs") else {
+        return None;
+    };
+
+    // Follows the references from the root class.
+    let mut needed = HashSet::new();
+    let mut queue = vec![root.to_string()];
+    while let Some(name) = queue.pop() {
+        if let Some(definition) = definitions.get(&name).filter(|_| needed.insert(name.clone())) {
+            collect_references(definition, &mut queue);
+        }
+    }
+    let definitions = definitions.into_iter()
+        .filter(|(name, _)| needed.contains(name))
+        .collect::<Map<_, _>>();
+
+    schema.insert("title".to_owned(), Value::from(format!("{:?} {}", version, resource_type.to_uppercase())));
+    schema.insert("$ref".to_owned(), Value::from(format!("#/$defs/{}", root)));
+    schema.insert("$defs".to_owned(), Value::Object(definitions));
+    Some(Value::Object(schema))
+}
+
+fn collect_references(schema: &Value, references: &mut Vec<String>) {
+    match schema {
+        Value::Object(object) => {
+            for (key, value) in object {
+                match (key.as_str(), value.as_str().and_then(|reference| reference.strip_prefix("#/$defs/"))) {
+                    ("$ref", Some(name)) => references.push(name.to_owned()),
+                    _ => collect_references(value, references),
+                }
+            }
+        }
+        Value::Array(values) => values.iter().for_each(|value| collect_references(value, references)),
+        _ => {}
+    }
+}
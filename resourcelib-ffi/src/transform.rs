@@ -0,0 +1,69 @@
+//! World transforms of the sub-entities of a template, composed from their `m_mTransform` properties along their
+//! spatial parents, the entities their `m_eidParent` property refers to.
+
+use glacier_types::glam::{Affine3A, Vec3A};
+use glacier_types::{EntityTemplatePropertyId, ZVariant};
+use serde_json::Value;
+use crate::query::{property_id_from_name, EntitySource, EntityView};
+
+const TRANSFORM_PROPERTY: &str = "m_mTransform";
+const PARENT_PROPERTY: &str = "m_eidParent";
+
+/// The local transform of an entity, relative to its spatial parent. Entities without a `m_mTransform` are at the
+/// origin of their parent.
+pub fn local_transform(entity: &EntityView) -> Affine3A {
+    entity.properties.iter()
+        .filter(|property| is_property(property.id, TRANSFORM_PROPERTY))
+        .find_map(|property| matrix_from_variant(property.value))
+        .unwrap_or(Affine3A::IDENTITY)
+}
+
+/// The index of the spatial parent of an entity in its template, from its `m_eidParent` property. `None` if the
+/// property is missing, null or refers to an entity of another template.
+pub fn spatial_parent(entity: &EntityView) -> Option<usize> {
+    entity.properties.iter()
+        .filter(|property| is_property(property.id, PARENT_PROPERTY))
+        .find_map(|property| local_index_from_variant(property.value))
+}
+
+/// The world transform of every sub-entity, in template order. Entities without a local spatial parent are roots.
+pub fn world_transforms<S: EntitySource + ?Sized>(source: &S) -> Vec<Affine3A> {
+    let entities = source.entity_views();
+    let locals = entities.iter().map(local_transform).collect::<Vec<_>>();
+    let parents = entities.iter().map(spatial_parent).collect::<Vec<_>>();
+    glacier_types::math::world_transforms(&locals, &parents)
+}
+
+/// The world transform of a single sub-entity.
+pub fn world_transform<S: EntitySource + ?Sized>(source: &S, entity_index: usize) -> Option<Affine3A> {
+    world_transforms(source).get(entity_index).copied()
+}
+
+fn is_property(id: &EntityTemplatePropertyId, name: &str) -> bool {
+    match id {
+        EntityTemplatePropertyId::Str(property_name) => property_name == name,
+        EntityTemplatePropertyId::Num(id) => *id == property_id_from_name(name),
+    }
+}
+
+/// Reads the `entityIndex` of an `SEntityTemplateReference`, which is negative for null and external references.
+fn local_index_from_variant(value: &dyn ZVariant) -> Option<usize> {
+    if value.type_name() != "SEntityTemplateReference" {
+        return None;
+    }
+    let value = serde_json::to_value(value).ok()?;
+    usize::try_from(value["$val"]["entityIndex"].as_i64()?).ok()
+}
+
+/// Reads an `SMatrix43` through its JSON representation, which is the same in every version.
+fn matrix_from_variant(value: &dyn ZVariant) -> Option<Affine3A> {
+    if value.type_name() != "SMatrix43" {
+        return None;
+    }
+    let value = serde_json::to_value(value).ok()?;
+    let column = |name: &str| -> Option<Vec3A> {
+        let vector: &Value = &value["$val"][name];
+        Some(Vec3A::new(vector["x"].as_f64()? as f32, vector["y"].as_f64()? as f32, vector["z"].as_f64()? as f32))
+    };
+    Some(Affine3A::from_cols(column("XAxis")?, column("YAxis")?, column("ZAxis")?, column("Trans")?))
+}
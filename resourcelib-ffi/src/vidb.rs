@@ -0,0 +1,271 @@
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+#[cfg(feature = "hm2016")]
+use glacier_hm2016 as hm2016_bindings;
+#[cfg(feature = "hm2")]
+use glacier_hm2 as hm2_bindings;
+#[cfg(feature = "hm3")]
+use glacier_hm3 as hm3_bindings;
+use crate::ResourceLibError;
+
+const CSV_HEADER: [&str; 5] = ["key", "resource_id_high", "resource_id_low", "languages", "subtitle_languages"];
+
+/// Separates the languages within a single CSV field.
+const LANGUAGE_SEPARATOR: char = ';';
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoEntry {
+    pub key: String,
+    /// The `(m_IDHigh, m_IDLow)` pair of the video resource.
+    pub resource_id: (u32, u32),
+    pub languages: Vec<String>,
+    pub subtitle_languages: Vec<String>,
+}
+
+/// A version independent, editable copy of a video database (VIDB).
+///
+/// Keys are unique; every edit going through this type keeps them that way.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VideoDatabase {
+    entries: Vec<VideoEntry>,
+}
+
+impl VideoDatabase {
+    /// Creates a database from a list of entries, failing on the first duplicate key.
+    pub fn new(entries: Vec<VideoEntry>) -> Result<Self, ResourceLibError> {
+        let mut database = VideoDatabase::default();
+        for entry in entries {
+            database.add(entry)?;
+        }
+        Ok(database)
+    }
+
+    pub fn entries(&self) -> &[VideoEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&VideoEntry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+
+    /// Keys can only be changed through [`VideoDatabase::rename`], so entries aren't handed out mutably.
+    fn entry_mut(&mut self, key: &str) -> Result<&mut VideoEntry, ResourceLibError> {
+        self.entries.iter_mut()
+            .find(|entry| entry.key == key)
+            .ok_or_else(|| ResourceLibError::UnknownVideoKey(key.to_owned()))
+    }
+
+    pub fn set_resource_id(&mut self, key: &str, resource_id: (u32, u32)) -> Result<(), ResourceLibError> {
+        self.entry_mut(key)?.resource_id = resource_id;
+        Ok(())
+    }
+
+    pub fn set_languages(&mut self, key: &str, languages: Vec<String>) -> Result<(), ResourceLibError> {
+        self.entry_mut(key)?.languages = languages;
+        Ok(())
+    }
+
+    pub fn set_subtitle_languages(&mut self, key: &str, subtitle_languages: Vec<String>) -> Result<(), ResourceLibError> {
+        self.entry_mut(key)?.subtitle_languages = subtitle_languages;
+        Ok(())
+    }
+
+    /// Lists every language a video is available in, including subtitle-only languages.
+    pub fn variants(&self, key: &str) -> Option<Vec<&str>> {
+        let entry = self.get(key)?;
+        let mut variants = Vec::new();
+        for language in entry.languages.iter().chain(&entry.subtitle_languages) {
+            if !variants.contains(&language.as_str()) {
+                variants.push(language.as_str());
+            }
+        }
+        Some(variants)
+    }
+
+    pub fn add(&mut self, entry: VideoEntry) -> Result<(), ResourceLibError> {
+        if entry.key.is_empty() {
+            return Err(ResourceLibError::EmptyVideoKey);
+        }
+        if self.get(&entry.key).is_some() {
+            return Err(ResourceLibError::DuplicateVideoKey(entry.key));
+        }
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, key: &str) -> Result<VideoEntry, ResourceLibError> {
+        let index = self.entries.iter().position(|entry| entry.key == key)
+            .ok_or_else(|| ResourceLibError::UnknownVideoKey(key.to_owned()))?;
+        Ok(self.entries.remove(index))
+    }
+
+    pub fn rename(&mut self, key: &str, new_key: &str) -> Result<(), ResourceLibError> {
+        if new_key.is_empty() {
+            return Err(ResourceLibError::EmptyVideoKey);
+        }
+        if key != new_key && self.get(new_key).is_some() {
+            return Err(ResourceLibError::DuplicateVideoKey(new_key.to_owned()));
+        }
+        self.entry_mut(key)?.key = new_key.to_owned();
+        Ok(())
+    }
+
+    /// Writes the database as a CSV manifest, one entry per line. Languages are separated by `;`.
+    pub fn write_csv<W: Write>(&self, writer: &mut W) -> Result<(), ResourceLibError> {
+        writeln!(writer, "{}", CSV_HEADER.join(","))?;
+        for entry in &self.entries {
+            let fields = [
+                entry.key.clone(),
+                entry.resource_id.0.to_string(),
+                entry.resource_id.1.to_string(),
+                entry.languages.join(&LANGUAGE_SEPARATOR.to_string()),
+                entry.subtitle_languages.join(&LANGUAGE_SEPARATOR.to_string()),
+            ];
+            let line = fields.iter().map(|field| escape_csv_field(field)).collect::<Vec<_>>().join(",");
+            writeln!(writer, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a CSV manifest written by [`VideoDatabase::write_csv`], validating that all keys are unique.
+    pub fn read_csv<R: BufRead>(reader: R) -> Result<Self, ResourceLibError> {
+        let mut lines = reader.lines();
+        let header = lines.next().ok_or(ResourceLibError::InvalidCsv(1, "header"))??;
+        if split_csv_line(&header).ok().is_none_or(|header| header.iter().map(String::as_str).ne(CSV_HEADER)) {
+            return Err(ResourceLibError::InvalidCsv(1, "header"));
+        }
+
+        let mut database = VideoDatabase::default();
+        for (line_index, line) in lines.enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            // The header is line 1.
+            let line_number = line_index + 2;
+            let invalid = |name: &'static str| ResourceLibError::InvalidCsv(line_number, name);
+            let field_count = || ResourceLibError::CsvFieldCount(line_number, CSV_HEADER.len());
+            let fields = split_csv_line(&line)
+                .map_err(|index| CSV_HEADER.get(index).map_or_else(field_count, |&name| invalid(name)))?;
+            if fields.len() != CSV_HEADER.len() {
+                return Err(field_count());
+            }
+            let languages = |field: &str| {
+                field.split(LANGUAGE_SEPARATOR).map(str::trim).filter(|language| !language.is_empty()).map(str::to_owned).collect()
+            };
+            database.add(VideoEntry {
+                key: fields[0].clone(),
+                resource_id: (
+                    fields[1].parse().map_err(|_| invalid(CSV_HEADER[1]))?,
+                    fields[2].parse().map_err(|_| invalid(CSV_HEADER[2]))?,
+                ),
+                languages: languages(&fields[3]),
+                subtitle_languages: languages(&fields[4]),
+            })?;
+        }
+        Ok(database)
+    }
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Splits a single CSV line, honouring double quoted fields. Fails with the index of a field whose quotes aren't
+/// terminated.
+fn split_csv_line(line: &str) -> Result<Vec<String>, usize> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(fields.len());
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+pub trait VideoDatabaseData {
+    /// Copies the entries of the resource, failing if it contains duplicate keys.
+    fn to_video_database(&self) -> Result<VideoDatabase, ResourceLibError>;
+
+    /// Replaces the entries of the resource with those of the database.
+    fn apply_video_database(&mut self, database: &VideoDatabase);
+
+    /// Builds a resource from a database, e.g. one read from a CSV manifest, ready to be passed to a
+    /// [`ResourceParserTyped`](crate::codegen::ResourceParserTyped).
+    fn from_video_database(database: &VideoDatabase) -> Self where Self: Sized;
+}
+
+macro_rules! impl_video_database {
+    ($bindings:ident) => {
+        impl VideoDatabaseData for $bindings::properties::SVideoDatabaseData {
+            fn to_video_database(&self) -> Result<VideoDatabase, ResourceLibError> {
+                let mut keys = HashSet::new();
+                let entries = self.entries.iter()
+                    .map(|entry| {
+                        if !keys.insert(entry.s_key.as_str()) {
+                            return Err(ResourceLibError::DuplicateVideoKey(entry.s_key.to_string()));
+                        }
+                        Ok(VideoEntry {
+                            key: entry.s_key.to_string(),
+                            resource_id: (entry.rid.id_high, entry.rid.id_low),
+                            languages: entry.a_languages.iter().map(|language| language.to_string()).collect(),
+                            subtitle_languages: entry.a_subtitle_languages.iter().map(|language| language.to_string()).collect(),
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(VideoDatabase { entries })
+            }
+
+            fn apply_video_database(&mut self, database: &VideoDatabase) {
+                use $bindings::properties::{SVideoDatabaseEntry, ZRuntimeResourceId};
+
+                self.entries = database.entries.iter()
+                    .map(|entry| SVideoDatabaseEntry {
+                        s_key: entry.key.as_str().into(),
+                        rid: ZRuntimeResourceId { id_high: entry.resource_id.0, id_low: entry.resource_id.1 },
+                        a_languages: entry.languages.iter().map(|language| language.as_str().into()).collect(),
+                        a_subtitle_languages: entry.subtitle_languages.iter().map(|language| language.as_str().into()).collect(),
+                    })
+                    .collect();
+            }
+
+            fn from_video_database(database: &VideoDatabase) -> Self {
+                let mut data = $bindings::properties::SVideoDatabaseData { entries: Vec::new() };
+                data.apply_video_database(database);
+                data
+            }
+        }
+    };
+}
+
+#[cfg(feature = "hm2016")]
+impl_video_database!(hm2016_bindings);
+#[cfg(feature = "hm2")]
+impl_video_database!(hm2_bindings);
+#[cfg(feature = "hm3")]
+impl_video_database!(hm3_bindings);
@@ -0,0 +1,145 @@
+#![cfg(feature = "airg")]
+
+use resourcelib_ffi::airg::export::{visibility_heatmap, write_gltf, write_obj};
+use resourcelib_ffi::airg::{ReasoningGraph, Waypoint};
+use resourcelib_ffi::ResourceLibError;
+
+/// A row of four waypoints with an unconnected fifth one.
+fn graph() -> ReasoningGraph {
+    let mut graph = ReasoningGraph::default();
+    for x in 0..4 {
+        graph.add_waypoint(Waypoint::new([x as f32, 0.0, 0.0], 0)).unwrap();
+    }
+    graph.add_waypoint(Waypoint::new([10.0, 10.0, 0.0], 0)).unwrap();
+    for x in 0..3 {
+        graph.link(x, x + 1, 2).unwrap();
+    }
+    graph
+}
+
+#[test]
+fn test_find_path() {
+    let graph = graph();
+    assert_eq!(graph.find_path(0, 3).unwrap(), Some(vec![0, 1, 2, 3]));
+    assert_eq!(graph.find_path(3, 0).unwrap(), Some(vec![3, 2, 1, 0]));
+    assert_eq!(graph.find_path(0, 4).unwrap(), None);
+    assert!(graph.find_path(0, 5).is_err());
+}
+
+#[test]
+fn test_nearest_waypoint_and_components() {
+    let graph = graph();
+    assert_eq!(graph.nearest_waypoint([2.2, 0.5, 0.0]), Some(2));
+    assert_eq!(graph.connected_components(), vec![vec![0, 1, 2, 3], vec![4]]);
+    assert_eq!(graph.isolated_waypoints(), vec![4]);
+}
+
+#[test]
+fn test_remove_waypoint() {
+    let mut graph = graph();
+    graph.remove_waypoint(1).unwrap();
+
+    assert_eq!(graph.len(), 4);
+    assert_eq!(graph.waypoints[0].neighbors[2], None);
+    assert_eq!(graph.waypoints[1].neighbors[2], Some(2));
+    assert_eq!(graph.connected_components(), vec![vec![0], vec![1, 2], vec![3]]);
+}
+
+#[test]
+fn test_invalid_arguments() {
+    let mut graph = graph();
+    assert!(matches!(graph.link(0, 1, 8), Err(ResourceLibError::InvalidNeighborDirection(8))));
+    assert!(matches!(graph.link(0, 5, 2), Err(ResourceLibError::InvalidWaypoint(5))));
+    assert!(matches!(visibility_heatmap(&graph, 0.0), Err(ResourceLibError::InvalidCellSize(_))));
+}
+
+#[test]
+#[cfg(feature = "hm3")]
+fn test_apply_graph() {
+    use glacier_hm3::properties::SReasoningGrid;
+    use resourcelib_ffi::airg::ReasoningGridData;
+
+    let mut graph = graph();
+    graph.waypoints[0].low_visibility = true;
+    graph.waypoints[3].high_visibility = true;
+    graph.waypoints[4].dead_end = true;
+    for (index, waypoint) in graph.waypoints.iter_mut().enumerate() {
+        waypoint.visibility_data = vec![index as u8; 2];
+    }
+
+    let mut grid = SReasoningGrid::default();
+    grid.apply_graph(&graph).unwrap();
+    assert_eq!(grid.to_graph(), graph);
+    assert_eq!(grid.n_node_count, 5);
+    assert_eq!(grid.low_visibility_bits.n_size, 5);
+    assert_eq!((grid.properties.v_max.x, grid.properties.v_max.y), (10.0, 10.0));
+
+    // Removing the far waypoint shrinks the bounds and every bit array.
+    graph.remove_waypoint(4).unwrap();
+    grid.apply_graph(&graph).unwrap();
+    assert_eq!(grid.to_graph(), graph);
+    assert_eq!((grid.high_visibility_bits.a_bytes.clone(), grid.high_visibility_bits.n_size), (vec![0b1000], 4));
+    assert_eq!(grid.dead_end_data.a_bytes, vec![0]);
+    assert_eq!((grid.properties.v_max.x, grid.properties.v_max.y), (3.0, 0.0));
+}
+
+#[test]
+fn test_export_obj_and_gltf() {
+    let graph = graph();
+
+    let mut obj = Vec::new();
+    write_obj(&graph, &mut obj).unwrap();
+    let obj = String::from_utf8(obj).unwrap();
+    assert_eq!(obj.lines().filter(|line| line.starts_with("v ")).count(), 5);
+    assert_eq!(obj.lines().filter(|line| line.starts_with("l ")).collect::<Vec<_>>(), vec!["l 1 2", "l 2 3", "l 3 4"]);
+
+    let mut gltf = Vec::new();
+    write_gltf(&graph, &mut gltf).unwrap();
+    let gltf: serde_json::Value = serde_json::from_slice(&gltf).unwrap();
+    assert_eq!(gltf["accessors"][0]["count"], 5);
+    assert_eq!(gltf["accessors"][1]["count"], 6);
+    assert_eq!(gltf["buffers"][0]["byteLength"], 5 * 12 + 3 * 8);
+}
+
+#[test]
+fn test_export_empty_graphs() {
+    let gltf = |graph: &ReasoningGraph| {
+        let mut gltf = Vec::new();
+        write_gltf(graph, &mut gltf).unwrap();
+        serde_json::from_slice::<serde_json::Value>(&gltf).unwrap()
+    };
+
+    let empty = gltf(&ReasoningGraph::default());
+    assert!(empty.get("accessors").is_none() && empty.get("buffers").is_none());
+    assert!(empty["nodes"][0].get("mesh").is_none());
+
+    let mut unlinked = ReasoningGraph::default();
+    unlinked.add_waypoint(Waypoint::new([1.0, 2.0, 3.0], 0)).unwrap();
+    let unlinked = gltf(&unlinked);
+    assert_eq!(unlinked["accessors"].as_array().map(Vec::len), Some(1));
+    assert!(unlinked["meshes"][0]["primitives"][0].get("indices").is_none());
+    assert_eq!(unlinked["meshes"][0]["primitives"][0]["mode"], 0);
+
+    #[cfg(feature = "png")]
+    {
+        let heatmap = visibility_heatmap(&ReasoningGraph::default(), 1.0).unwrap();
+        assert!(matches!(heatmap.write_png(Vec::new()), Err(ResourceLibError::EmptyHeatmap)));
+    }
+}
+
+#[test]
+fn test_visibility_heatmap() {
+    let mut graph = graph();
+    graph.waypoints[0].visibility_data = vec![0xFF];
+    graph.waypoints[1].visibility_data = vec![0x0F];
+
+    let heatmap = visibility_heatmap(&graph, 1.0).unwrap();
+    assert_eq!((heatmap.width, heatmap.height), (11, 11));
+    assert_eq!(heatmap.get(0, 10), Some(1.0));
+    assert_eq!(heatmap.get(1, 10), Some(0.5));
+    assert_eq!(heatmap.get(5, 5), None);
+
+    let mut svg = Vec::new();
+    heatmap.write_svg(&mut svg).unwrap();
+    assert_eq!(String::from_utf8(svg).unwrap().matches("<rect").count(), 5);
+}
@@ -0,0 +1,67 @@
+#![cfg(all(feature = "atmd", feature = "hm2"))]
+
+use glacier_hm2::properties::ZamdTake;
+use resourcelib_ffi::atmd::{AnimationEvent, AnimationTake, AnimationTakeData, AnimationTrack};
+use resourcelib_ffi::ResourceLibError;
+
+fn take() -> AnimationTake {
+    AnimationTake {
+        name: "Door_Open".to_string(),
+        duration: 2.0,
+        tracks: vec![
+            AnimationTrack {
+                name: "Sound".to_string(),
+                events: vec![
+                    AnimationEvent { name: "Slam".to_string(), time: 1.5, duration: 0.25 },
+                    AnimationEvent { name: "Creak".to_string(), time: 0.25, duration: 1.0 },
+                ],
+            },
+            AnimationTrack { name: "Effects".to_string(), events: vec![] },
+        ],
+        animations: vec![(0x00123456, 0x789ABCDE)],
+    }
+}
+
+#[test]
+fn test_edit_event_timing() {
+    let mut take = take();
+    assert_eq!(take.event_count(), 2);
+    assert!(take.track("Effects").is_some_and(|track| track.events.is_empty()));
+
+    take.set_event_timing(0, 1, 0.5, 0.5).unwrap();
+    assert_eq!(take.tracks[0].events[1].end(), 1.0);
+    assert!(matches!(take.set_event_timing(0, 0, 1.9, 0.5), Err(ResourceLibError::InvalidAnimationEvent(0, 0))));
+    assert!(matches!(take.set_event_timing(1, 0, 0.0, 0.0), Err(ResourceLibError::InvalidAnimationEvent(1, 0))));
+
+    assert!(take.shift_track(0, 0.5).is_err());
+    take.shift_track(0, -0.5).unwrap();
+    assert_eq!(take.tracks[0].events[0].time, 1.0);
+
+    take.rescale(4.0);
+    assert_eq!(take.tracks[0].events[0].time, 2.0);
+    assert_eq!(take.tracks[0].events[0].duration, 0.5);
+}
+
+#[test]
+fn test_timeline_report() {
+    let mut report = Vec::new();
+    take().write_timeline(&mut report).unwrap();
+    let report = String::from_utf8(report).unwrap();
+
+    assert!(report.starts_with("Take \"Door_Open\": 2.000s, 2 tracks, 2 events"));
+    assert!(report.contains("[0] 00123456789ABCDE"));
+    assert!(report.find("Creak").unwrap() < report.find("Slam").unwrap());
+}
+
+#[test]
+fn test_resource_round_trip() {
+    let take = take();
+    let mut data: ZamdTake = serde_json::from_value(serde_json::json!({
+        "m_sName": "",
+        "m_fDuration": 0.0,
+        "m_aTracks": [],
+        "m_aAnimations": []
+    })).unwrap();
+    data.apply_animation_take(&take);
+    assert_eq!(data.to_animation_take(), take);
+}
@@ -0,0 +1,112 @@
+#![cfg(all(feature = "rust-backend", feature = "vidb", feature = "crmd", feature = "gidx", feature = "hm3"))]
+
+use glacier_hm3::properties::{SCrowdMapData, SResourceIndex, SResourceIndexFolder, SVector4, SVideoDatabaseData};
+use glacier_types::ZString;
+use resourcelib_ffi::rust_backend::{read_resource, supported_resource_types, write_resource};
+use resourcelib_ffi::vidb::{VideoDatabase, VideoDatabaseData, VideoEntry};
+use resourcelib_ffi::{ResourceConverter, ResourceGenerator, ResourceLibError, WoaVersion};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+fn video_database() -> SVideoDatabaseData {
+    let entry = |key: &str, id_low: u32, languages: &[&str]| VideoEntry {
+        key: key.to_string(),
+        resource_id: (0x00123456, id_low),
+        languages: languages.iter().map(|language| language.to_string()).collect(),
+        subtitle_languages: vec![],
+    };
+    let database = VideoDatabase::new(vec![entry("intro", 1, &["en", "fr"]), entry("outro", 2, &[])]).unwrap();
+    SVideoDatabaseData::from_video_database(&database)
+}
+
+fn crowd_map() -> SCrowdMapData {
+    SCrowdMapData {
+        v_min: SVector4 { x: -2.0, y: -1.0, z: 0.0, w: 1.0 },
+        v_max: SVector4 { x: 2.0, y: 1.0, z: 1.0, w: 1.0 },
+        n_cell_count_x: 4,
+        n_cell_count_y: 2,
+        f_cell_size: 1.0,
+        a_cell_flags: vec![0, 1, 1, 0, 1, 0, 0, 4],
+    }
+}
+
+fn resource_index() -> SResourceIndex {
+    let folder = |name: &str, resource_indices: Vec<i32>| SResourceIndexFolder { name: ZString::from(name), resource_indices };
+    SResourceIndex {
+        folders: vec![folder("assembly:/_pro/scenes", vec![0, 3, 4]), folder("assembly:/_pro/empty", vec![])],
+    }
+}
+
+/// Generates a resource from the JSON of `value` and converts it back with the Rust backend.
+fn check_round_trip<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(resource_type: &str, value: &T) {
+    let generator = ResourceGenerator::rust(WoaVersion::HM3, resource_type).unwrap();
+    let converter = ResourceConverter::rust(WoaVersion::HM3, resource_type).unwrap();
+
+    let resource_data = generator.json_string_to_resource_mem(&serde_json::to_string(value).unwrap(), false).unwrap();
+    assert_eq!(&resource_data[..4], b"BIN1");
+    let json = converter.memory_to_json_string(&resource_data).unwrap();
+    assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), value);
+}
+
+#[test]
+fn test_round_trip() {
+    check_round_trip("VIDB", &video_database());
+    check_round_trip("CRMD", &crowd_map());
+    check_round_trip("GIDX", &resource_index());
+    assert_eq!(supported_resource_types(WoaVersion::HM3), ["VIDB", "CRMD", "GIDX"]);
+}
+
+#[test]
+fn test_resource_layout() {
+    let resource_data = write_resource(&crowd_map());
+    // The header holds the size of the data in big endian.
+    let data_size = u32::from_be_bytes(resource_data[8..12].try_into().unwrap()) as usize;
+    assert_eq!(resource_data[5], 16);
+    assert_eq!(resource_data[6], 1);
+    // The count of the cell flags precedes them, after the root.
+    let count = 16 + SCrowdMapData::SIZE;
+    assert_eq!(u32::from_le_bytes(resource_data[count..count + 4].try_into().unwrap()), 8);
+    assert_eq!(u32::from_le_bytes(resource_data[16 + data_size..16 + data_size + 4].try_into().unwrap()), 0x12EBA5ED);
+    assert_eq!(read_resource::<SCrowdMapData>(&resource_data).unwrap(), crowd_map());
+    assert_eq!(write_resource(&read_resource::<SCrowdMapData>(&resource_data).unwrap()), resource_data);
+
+    // Strings and runtime resource IDs need both segments.
+    let resource_data = write_resource(&video_database());
+    assert_eq!(resource_data[6], 2);
+    assert_eq!(read_resource::<SVideoDatabaseData>(&resource_data).unwrap(), video_database());
+
+    assert!(matches!(read_resource::<SCrowdMapData>(b"BIN1"), Err(ResourceLibError::InvalidResource(_))));
+    assert!(matches!(ResourceConverter::rust(WoaVersion::HM3, "TEMP"), Err(ResourceLibError::InvalidResourceType(_))));
+}
+
+/// Compares the Rust backend to ResourceLib: both have to generate the same bytes from the JSON of `value`, and read
+/// the same value from them.
+#[cfg(feature = "native")]
+fn check_against_resourcelib<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(resource_type: &str, value: &T) {
+    let json = serde_json::to_string(value).unwrap();
+    let native_generator = ResourceGenerator::new(WoaVersion::HM3, resource_type).unwrap();
+    let rust_generator = ResourceGenerator::rust(WoaVersion::HM3, resource_type).unwrap();
+    let native_data = native_generator.json_string_to_resource_mem(&json, false).unwrap();
+    let rust_data = rust_generator.json_string_to_resource_mem(&json, false).unwrap();
+    assert_eq!(rust_data, native_data, "{} bytes differ", resource_type);
+
+    let native_converter = ResourceConverter::new(WoaVersion::HM3, resource_type).unwrap();
+    let rust_converter = ResourceConverter::rust(WoaVersion::HM3, resource_type).unwrap();
+    for data in [&native_data, &rust_data] {
+        let native_json = native_converter.memory_to_json_string(data).unwrap();
+        let rust_json = rust_converter.memory_to_json_string(data).unwrap();
+        assert_eq!(&serde_json::from_str::<T>(&native_json).unwrap(), value);
+        assert_eq!(&serde_json::from_str::<T>(&rust_json).unwrap(), value);
+    }
+}
+
+#[test]
+#[cfg(feature = "native")]
+fn test_matches_resourcelib() {
+    check_against_resourcelib("VIDB", &video_database());
+    check_against_resourcelib("CRMD", &crowd_map());
+    check_against_resourcelib("GIDX", &resource_index());
+    check_against_resourcelib("VIDB", &SVideoDatabaseData::default());
+    check_against_resourcelib("CRMD", &SCrowdMapData { a_cell_flags: vec![], ..crowd_map() });
+    check_against_resourcelib("GIDX", &SResourceIndex::default());
+}
@@ -0,0 +1,124 @@
+#![cfg(all(feature = "all-types", feature = "hm3"))]
+
+use glacier_hm3::properties::{SColorRgba, SVector3};
+use glacier_types::binary::{from_bytes, to_bytes, BinaryError, BinaryReader, BinaryWriter, GameStruct};
+
+/// Copies a buffer to memory aligned to 16 bytes and relocates its pointers to their addresses.
+fn load(data: &[u8], relocations: &[usize]) -> Vec<u128> {
+    let mut memory = vec![0u128; data.len().div_ceil(16)];
+    let bytes = unsafe { std::slice::from_raw_parts_mut(memory.as_mut_ptr() as *mut u8, data.len()) };
+    bytes.copy_from_slice(data);
+    let base = bytes.as_ptr() as u64;
+    for &offset in relocations {
+        let pointer = u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap()) + base;
+        bytes[offset..offset + 8].copy_from_slice(&pointer.to_le_bytes());
+    }
+    memory
+}
+
+/// Writes a value with the Rust codec and checks that ResourceLib reads the same value from it.
+#[cfg(feature = "native")]
+fn check_against_resourcelib<T>(value: &T)
+where
+    T: GameStruct + glacier_types::reflect::Reflect + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+{
+    use resourcelib_ffi::{ResourceLib, WoaVersion};
+
+    let (data, relocations) = to_bytes(value);
+    let memory = load(&data, &relocations);
+    // ResourceLib is only given the root struct; the elements of its arrays and strings follow it in memory.
+    let json = unsafe {
+        let bytes = std::slice::from_raw_parts(memory.as_ptr() as *const u8, T::SIZE);
+        ResourceLib::game_struct_to_json(WoaVersion::HM3, value.type_info().name, bytes).unwrap()
+    }.unwrap();
+    assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), value);
+}
+
+#[test]
+fn test_read_write_plain_classes() {
+    let vector = SVector3 { x: 1.5, y: -2.0, z: 0.25 };
+    let (data, relocations) = to_bytes(&vector);
+    assert_eq!(data, [1.5f32, -2.0, 0.25].iter().flat_map(|value| value.to_le_bytes()).collect::<Vec<_>>());
+    assert!(relocations.is_empty());
+    assert_eq!(from_bytes::<SVector3>(&data).unwrap(), vector);
+
+    let color = SColorRgba { r: 0.5, g: 1.0, b: 0.0, a: 1.0 };
+    assert_eq!(from_bytes::<SColorRgba>(&to_bytes(&color).0).unwrap(), color);
+
+    assert_eq!(from_bytes::<SVector3>(&data[..8]), Err(BinaryError::OutOfBounds { offset: 8, len: 4, size: 8 }));
+}
+
+#[test]
+fn test_read_write_arrays() {
+    let vectors = vec![SVector3 { x: 1.0, y: 2.0, z: 3.0 }, SVector3 { x: 4.0, y: 5.0, z: 6.0 }];
+    let (data, relocations) = to_bytes(&vectors);
+    // The elements follow the three pointers of the array.
+    assert_eq!(data.len(), 24 + 2 * SVector3::SIZE);
+    assert_eq!(relocations, [0, 8, 16]);
+    assert_eq!(u64::from_le_bytes(data[..8].try_into().unwrap()), 24);
+    assert_eq!(from_bytes::<Vec<SVector3>>(&data).unwrap(), vectors);
+
+    // Relocated to the addresses of the buffer.
+    let memory = load(&data, &relocations);
+    let bytes = unsafe { std::slice::from_raw_parts(memory.as_ptr() as *const u8, data.len()) };
+    let reader = BinaryReader::with_base(bytes, bytes.as_ptr() as u64);
+    assert_eq!(reader.read::<Vec<SVector3>>(0).unwrap(), vectors);
+
+    let (data, relocations) = to_bytes(&Vec::<u32>::new());
+    assert_eq!((data, relocations), (vec![0; 24], vec![]));
+    assert_eq!(from_bytes::<[Vec<u32>; 2]>(&to_bytes(&[vec![1u32, 2], vec![3]]).0).unwrap(), [vec![1, 2], vec![3]]);
+}
+
+/// A class without fields.
+#[derive(Debug, PartialEq)]
+struct Empty;
+
+impl GameStruct for Empty {
+    const SIZE: usize = 0;
+    const ALIGN: usize = 1;
+
+    fn read(_reader: &BinaryReader, _offset: usize) -> Result<Self, BinaryError> {
+        Ok(Empty)
+    }
+
+    fn write(&self, _writer: &mut BinaryWriter, _offset: usize) {}
+}
+
+#[test]
+fn test_read_write_arrays_without_size() {
+    // Only the resource format stores how many elements there are.
+    let mut writer = BinaryWriter::resource();
+    writer.append(&vec![Empty, Empty]);
+    assert_eq!(BinaryReader::resource(writer.data()).read::<Vec<Empty>>(0).unwrap(), [Empty, Empty]);
+
+    let (data, _) = to_bytes(&vec![Empty]);
+    assert_eq!(from_bytes::<Vec<Empty>>(&data), Err(BinaryError::InvalidArray { offset: 0 }));
+    assert_eq!(from_bytes::<Vec<Empty>>(&to_bytes(&Vec::<Empty>::new()).0).unwrap(), []);
+}
+
+#[test]
+#[cfg(feature = "native")]
+fn test_matches_resourcelib() {
+    use glacier_hm3::properties::{SCrowdMapData, SVector2, SVector4, SVideoDatabaseEntry, ZRuntimeResourceId};
+    use glacier_types::ZString;
+
+    check_against_resourcelib(&SVector3 { x: 1.5, y: -2.0, z: 0.25 });
+    check_against_resourcelib(&SVector2 { x: 0.0, y: -0.5 });
+    check_against_resourcelib(&SColorRgba { r: 0.5, g: 1.0, b: 0.0, a: 1.0 });
+
+    // TArrays and ZStrings, whose pointers are relocated.
+    check_against_resourcelib(&SCrowdMapData {
+        v_min: SVector4 { x: -2.0, y: -1.0, z: 0.0, w: 1.0 },
+        v_max: SVector4 { x: 2.0, y: 1.0, z: 1.0, w: 1.0 },
+        n_cell_count_x: 2,
+        n_cell_count_y: 1,
+        f_cell_size: 2.0,
+        a_cell_flags: vec![1, 4],
+    });
+    check_against_resourcelib(&SVideoDatabaseEntry {
+        s_key: ZString::from("intro"),
+        rid: ZRuntimeResourceId { id_high: 0x00123456, id_low: 1 },
+        a_languages: vec![ZString::from("en"), ZString::from("fr")],
+        a_subtitle_languages: vec![],
+    });
+}
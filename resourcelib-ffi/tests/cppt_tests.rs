@@ -0,0 +1,67 @@
+#![cfg(all(feature = "cppt", feature = "cblu", feature = "hm3"))]
+
+use glacier_hm3::properties::{SCppEntity, SCppEntityBlueprint};
+use resourcelib_ffi::cppt::{CppEntityReflection, PropertyDeclarations, PropertyMismatch};
+use resourcelib_ffi::query::property_id_from_name;
+use serde_json::json;
+
+fn blueprint() -> SCppEntityBlueprint {
+    serde_json::from_value(json!({
+        "typeName": "ZActorSpawnEntity",
+        "subsets": [{ "name": "Actors", "type": "ZActorSpawnEntity", "flags": 0 }]
+    })).unwrap()
+}
+
+fn entity() -> SCppEntity {
+    serde_json::from_value(json!({
+        "blueprintIndexInResourceHeader": 0,
+        "propertyValues": [
+            { "nPropertyID": property_id_from_name("m_sActorName"), "value": { "$type": "ZString", "$val": "Guard" } },
+            { "nPropertyID": property_id_from_name("m_nHealth"), "value": { "$type": "bool", "$val": true } },
+            { "nPropertyID": "m_bEnabled", "value": { "$type": "bool", "$val": true } },
+            { "nPropertyID": property_id_from_name("m_sActorName"), "value": { "$type": "ZString", "$val": "Civilian" } }
+        ]
+    })).unwrap()
+}
+
+fn names(id: u32) -> Option<String> {
+    ["m_sActorName", "m_nHealth"].into_iter().find(|name| property_id_from_name(name) == id).map(str::to_string)
+}
+
+#[test]
+fn test_reflect_properties() {
+    let (blueprint, entity) = (blueprint(), entity());
+    let reflection = CppEntityReflection::new(&blueprint, &entity, names);
+
+    assert_eq!(reflection.type_name, "ZActorSpawnEntity");
+    assert_eq!(reflection.subsets[0].name, "Actors");
+    assert_eq!(reflection.property("m_nHealth").and_then(|property| property.name.as_deref()), Some("m_nHealth"));
+    assert_eq!(reflection.property("m_bEnabled").map(|property| property.type_name.as_str()), Some("bool"));
+    assert_eq!(CppEntityReflection::new(&blueprint, &entity, |_| None).properties[0].name, None);
+}
+
+#[test]
+fn test_validate_properties() {
+    let (blueprint, entity) = (blueprint(), entity());
+    let reflection = CppEntityReflection::new(&blueprint, &entity, names);
+
+    let mut declarations = PropertyDeclarations::new();
+    declarations.declare("ZActorSpawnEntity", "m_sActorName", "ZString");
+    declarations.declare("ZActorSpawnEntity", "m_nHealth", "int32");
+
+    let mismatches = reflection.validate(&declarations);
+    assert_eq!(mismatches, vec![
+        PropertyMismatch::Type { index: 1, property: "m_nHealth".to_string(), expected: "int32".to_string(), found: "bool".to_string() },
+        PropertyMismatch::Undeclared { index: 2, property: "m_bEnabled".to_string() },
+        PropertyMismatch::Duplicate { index: 3, property: "m_sActorName".to_string() },
+    ]);
+
+    let mut learned = PropertyDeclarations::new();
+    learned.learn(&blueprint, &entity, names);
+    assert_eq!(learned.properties("ZActorSpawnEntity").count(), 3);
+    assert_eq!(reflection.validate(&learned).len(), 1);
+
+    let mut report = Vec::new();
+    reflection.write_report(&mut report, &declarations).unwrap();
+    assert!(String::from_utf8(report).unwrap().contains("propertyValues[1] m_nHealth: expected int32, found bool"));
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "crmd")]
+
+use resourcelib_ffi::crmd::{CrowdMap, WALKABLE};
+use resourcelib_ffi::ResourceLibError;
+
+fn crowd_map() -> CrowdMap {
+    CrowdMap {
+        min: [-2.0, -1.0, 0.0],
+        max: [2.0, 1.0, 1.0],
+        cell_size: 1.0,
+        width: 4,
+        height: 2,
+        cells: vec![0, WALKABLE, WALKABLE, 0, WALKABLE, 0, 0, 4],
+    }
+}
+
+#[test]
+fn test_cell_lookup() {
+    let map = crowd_map();
+    assert_eq!(map.cell_at(-1.5, -0.5), Some((0, 0)));
+    assert_eq!(map.cell_at(1.5, 0.5), Some((3, 1)));
+    assert_eq!(map.cell_at(2.5, 0.0), None);
+    assert!(map.is_walkable_at(-0.5, -0.5));
+    assert!(!map.is_walkable_at(-0.5, 0.5));
+    assert_eq!(map.flags_at(1.5, 0.5), Some(4));
+    assert_eq!(map.walkable_cell_count(), 3);
+}
+
+#[test]
+fn test_csv_round_trip() {
+    let map = crowd_map();
+    let mut csv = Vec::new();
+    map.write_csv(&mut csv).unwrap();
+
+    let mut edited = crowd_map();
+    edited.cells.fill(0);
+    edited.read_csv(csv.as_slice()).unwrap();
+    assert_eq!(edited, map);
+
+    edited.set_walkable(3, 1, true).unwrap();
+    assert_eq!(edited.flags(3, 1), Some(4 | WALKABLE));
+    assert!(edited.set_walkable(4, 1, true).is_err());
+}
+
+#[test]
+fn test_csv_errors() {
+    let mut map = crowd_map();
+    let error = map.read_csv("column,row,flags\n0,0,1\n1,x,1\n".as_bytes()).unwrap_err();
+    assert!(matches!(error, ResourceLibError::InvalidCsv(3, "row")), "{}", error);
+    let error = map.read_csv("column,flags\n".as_bytes()).unwrap_err();
+    assert!(matches!(error, ResourceLibError::InvalidCsv(1, "row")), "{}", error);
+    let error = map.read_csv("column,row,flags\n0,0\n".as_bytes()).unwrap_err();
+    assert!(matches!(error, ResourceLibError::InvalidCsv(2, "flags")), "{}", error);
+}
+
+#[test]
+#[cfg(feature = "hm3")]
+fn test_invalid_resources() {
+    use glacier_hm3::properties::SCrowdMapData;
+    use resourcelib_ffi::crmd::CrowdMapData;
+
+    let data = SCrowdMapData { n_cell_count_x: 1, n_cell_count_y: 1, f_cell_size: 0.0, a_cell_flags: vec![0], ..Default::default() };
+    assert!(matches!(data.to_crowd_map(), Err(ResourceLibError::InvalidCellSize(0.0))));
+    let data = SCrowdMapData { f_cell_size: 1.0, a_cell_flags: vec![], ..data };
+    assert!(matches!(data.to_crowd_map(), Err(ResourceLibError::ConversionFailed(_))));
+}
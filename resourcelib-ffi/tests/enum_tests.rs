@@ -0,0 +1,66 @@
+#![cfg(all(feature = "all-types", feature = "hm3"))]
+
+use glacier_types::ir::TypeModel;
+use glacier_types::ZVariant;
+use serde_json::json;
+
+/// Every `(enum, canonical variant, alias)` of the enums that declare a value more than once.
+fn aliases(model: &TypeModel) -> Vec<(&str, &str, &str)> {
+    let mut aliases = Vec::new();
+    for enum_decl in &model.enums {
+        for (index, variant) in enum_decl.variants.iter().enumerate() {
+            if let Some(canonical) = enum_decl.variants[..index].iter().find(|canonical| canonical.value == variant.value) {
+                aliases.push((enum_decl.name.as_str(), canonical.name.as_str(), variant.name.as_str()));
+            }
+        }
+    }
+    aliases
+}
+
+/// The name the enum is registered under as a `ZVariant`.
+fn type_name(enum_name: &str) -> String {
+    match enum_name.rfind('_') {
+        Some(pos) if !enum_name.contains("eParticleEmitterBoxEntity") => format!("{}.{}", &enum_name[..pos], &enum_name[pos + 1..]),
+        _ => enum_name.to_owned(),
+    }
+}
+
+#[test]
+fn test_zhmgen_declares_aliases() {
+    assert!(!aliases(glacier_hm3::type_model()).is_empty());
+}
+
+#[test]
+fn test_aliases_deserialize_to_the_canonical_variant() {
+    // Enums that aren't shared are registered once per enabled version, so only check them with one version enabled.
+    let mut checked = aliases(glacier_types::common::type_model());
+    if cfg!(not(any(feature = "hm2016", feature = "hm2"))) {
+        checked.extend(aliases(glacier_hm3::type_model()));
+    }
+
+    for (enum_name, canonical, alias) in checked {
+        let type_name = type_name(enum_name);
+        let expected = json!({ "$type": type_name, "$val": canonical });
+
+        let variant: Box<dyn ZVariant> = serde_json::from_value(json!({ "$type": type_name, "$val": alias })).unwrap();
+        assert_eq!(serde_json::to_value(&variant).unwrap(), expected, "{enum_name}::{alias}");
+
+        let variant: Box<dyn ZVariant> = serde_json::from_value(expected.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&variant).unwrap(), expected, "{enum_name}::{canonical}");
+    }
+}
+
+#[test]
+#[cfg(feature = "unknown-enum-values")]
+fn test_unknown_values_round_trip() {
+    use glacier_hm3::enums::EActorType;
+    use glacier_types::UnknownEnumValue;
+
+    assert_eq!(serde_json::from_value::<EActorType>(json!("eAT_Guard")).unwrap(), EActorType::Guard);
+    for value in [json!("eAT_FromANewerPatch"), json!(42)] {
+        let actor_type = serde_json::from_value::<EActorType>(value.clone()).unwrap();
+        assert!(matches!(actor_type, EActorType::Unknown(_)));
+        assert_eq!(serde_json::to_value(&actor_type).unwrap(), value);
+    }
+    assert_eq!(serde_json::from_value::<EActorType>(json!(42)).unwrap(), EActorType::Unknown(UnknownEnumValue::Value(42)));
+}
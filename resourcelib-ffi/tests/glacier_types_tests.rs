@@ -0,0 +1,74 @@
+#![cfg(all(feature = "temp", feature = "hm3"))]
+
+use glacier_hm3::properties::SEntityTemplateProperty;
+use glacier_types::{TypeId, ZEncryptedString, ZHMArena, ZHMArenas, ZRepositoryId, ZVariant};
+use serde_json::json;
+
+fn property_round_trip(value: serde_json::Value) -> Box<dyn ZVariant> {
+    let json = json!({ "nPropertyID": "m_sValue", "value": value });
+    let property: SEntityTemplateProperty = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&property).unwrap(), json);
+    property.value
+}
+
+#[test]
+fn test_repository_id_round_trip() {
+    let value = property_round_trip(json!({ "$type": "ZRepositoryID", "$val": "0a1b2c3d-4e5f-6a7b-8c9d-0e1f2a3b4c5d" }));
+    let id = value.as_any().downcast_ref::<ZRepositoryId>().unwrap();
+    assert_eq!(*id, ZRepositoryId(0x0a1b2c3d_4e5f_6a7b_8c9d_0e1f2a3b4c5d));
+    assert_eq!(id.to_string(), "0a1b2c3d-4e5f-6a7b-8c9d-0e1f2a3b4c5d");
+
+    assert!("0a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d".parse::<ZRepositoryId>().is_err());
+    assert!("+a1b2c3d-4e5f-6a7b-8c9d-0e1f2a3b4c5d".parse::<ZRepositoryId>().is_err());
+
+    let array = property_round_trip(json!({ "$type": "TArray<ZRepositoryID>", "$val": ["00000000-0000-0000-0000-000000000001"] }));
+    assert!(array.as_any().downcast_ref::<glacier_types::TArray<ZRepositoryId>>().is_some());
+}
+
+#[test]
+fn test_encrypted_string_and_arenas_round_trip() {
+    let value = property_round_trip(json!({ "$type": "ZEncryptedString", "$val": "secret" }));
+    assert_eq!(value.as_any().downcast_ref::<ZEncryptedString>(), Some(&ZEncryptedString::from("secret")));
+
+    let arena = json!({ "m_Id": 1, "m_TypeIndex": 3, "m_ObjectCount": 12 });
+    let value = property_round_trip(json!({ "$type": "ZHMArenas", "$val": [arena] }));
+    let arenas = value.as_any().downcast_ref::<ZHMArenas>().unwrap();
+    assert_eq!(arenas.0, [ZHMArena { id: 1, type_index: 3, object_count: 12 }]);
+    assert!(serde_json::from_value::<ZHMArenas>(json!([{ "m_Id": 1 }])).is_err());
+}
+
+#[test]
+fn test_type_id_round_trip() {
+    let type_id: TypeId = serde_json::from_value(json!("TArray<SVector3>")).unwrap();
+    assert_eq!(type_id.name(), "TArray<SVector3>");
+    assert_eq!(serde_json::to_value(&type_id).unwrap(), json!("TArray<SVector3>"));
+}
+
+/// Generates a TEMP with ResourceLib from properties holding these types and converts it back.
+#[test]
+#[cfg(feature = "native")]
+fn test_generator_round_trip() {
+    use glacier_hm3::properties::{STemplateEntityFactory, STemplateFactorySubEntity};
+    use resourcelib_ffi::{ResourceConverter, ResourceGenerator, WoaVersion};
+
+    let property = |value: serde_json::Value| {
+        serde_json::from_value(json!({ "nPropertyID": "m_sValue", "value": value })).unwrap()
+    };
+    let factory = STemplateEntityFactory {
+        sub_entities: vec![STemplateFactorySubEntity {
+            property_values: vec![
+                property(json!({ "$type": "ZRepositoryID", "$val": "0a1b2c3d-4e5f-6a7b-8c9d-0e1f2a3b4c5d" })),
+                property(json!({ "$type": "ZEncryptedString", "$val": "secret" })),
+                property(json!({ "$type": "ZHMArenas", "$val": [{ "m_Id": 1, "m_TypeIndex": 0, "m_ObjectCount": 2 }] })),
+            ],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let generator = ResourceGenerator::new(WoaVersion::HM3, "TEMP").unwrap();
+    let converter = ResourceConverter::new(WoaVersion::HM3, "TEMP").unwrap();
+    let resource_data = generator.json_string_to_resource_mem(&serde_json::to_string(&factory).unwrap(), false).unwrap();
+    let json = converter.memory_to_json_string(&resource_data).unwrap();
+    assert_eq!(serde_json::from_str::<STemplateEntityFactory>(&json).unwrap(), factory);
+}
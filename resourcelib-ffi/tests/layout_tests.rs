@@ -0,0 +1,122 @@
+#![cfg(all(feature = "native", feature = "all-types"))]
+#![cfg(any(feature = "hm2016", feature = "hm2", feature = "hm3"))]
+
+use glacier_types::ir::TypeRef;
+use glacier_types::reflect::TypeInfo;
+use resourcelib_ffi::{ResourceLib, WoaVersion};
+use serde_json::Value;
+
+/// The primitives written with a distinct value at their offset, by type name.
+const PRIMITIVES: [&str; 11] = ["bool", "int8", "int16", "int32", "int64", "uint8", "uint16", "uint32", "uint64", "float32", "float64"];
+
+/// Whether every byte of a type is a primitive, so a zeroed buffer is a valid instance for ResourceLib.
+fn is_plain(type_ref: &TypeRef, classes: &[&TypeInfo]) -> bool {
+    match (type_ref.name(), type_ref.args()) {
+        (Some(name), []) if PRIMITIVES.contains(&name) => true,
+        (Some("TFixedArray"), [inner, TypeRef::Value(_)]) => is_plain(inner, classes),
+        (Some(name), []) => classes.iter()
+            .find(|class| class.name == name)
+            .is_some_and(|class| class.fields.iter().all(|field| TypeRef::parse(field.type_name).is_ok_and(|field_type| is_plain(&field_type, classes)))),
+        _ => false,
+    }
+}
+
+/// Writes `value` as the primitive at the start of `bytes`.
+fn write_primitive(bytes: &mut [u8], type_name: &str, value: u8) {
+    let encoded = match type_name {
+        "float32" => f32::from(value).to_le_bytes().to_vec(),
+        "float64" => f64::from(value).to_le_bytes().to_vec(),
+        "bool" => vec![1],
+        "int8" | "uint8" => vec![value],
+        "int16" | "uint16" => u16::from(value).to_le_bytes().to_vec(),
+        "int32" | "uint32" => u32::from(value).to_le_bytes().to_vec(),
+        _ => u64::from(value).to_le_bytes().to_vec(),
+    };
+    bytes[..encoded.len()].copy_from_slice(&encoded);
+}
+
+fn game_struct_to_json(version: WoaVersion, class: &TypeInfo, buffer: &[u128], size: usize) -> Option<String> {
+    // The buffer is zeroed or holds primitives only, and is aligned to 16 bytes.
+    unsafe {
+        let bytes = std::slice::from_raw_parts(buffer.as_ptr() as *const u8, size);
+        ResourceLib::game_struct_to_json(version, class.name, bytes).unwrap()
+    }
+}
+
+/// Compares the generated layout of every plain class to the one of ResourceLib: it has to accept exactly `SIZE`
+/// bytes, and read every primitive field at its offset.
+fn check_layouts(version: WoaVersion, classes: &[&TypeInfo], all_classes: &[&TypeInfo]) {
+    for class in classes {
+        let plain = class.fields.iter().all(|field| TypeRef::parse(field.type_name).is_ok_and(|field_type| is_plain(&field_type, all_classes)));
+        let (Some(size), Some(alignment)) = (class.size, class.alignment) else {
+            continue;
+        };
+        if !plain || class.fields.is_empty() {
+            continue;
+        }
+        assert!(alignment <= 16, "{} is aligned to {} bytes", class.name, alignment);
+
+        let mut buffer = vec![0u128; size.div_ceil(16) + 1];
+        let bytes = unsafe { std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, size) };
+        let mut expected = Vec::new();
+        for (index, field) in class.fields.iter().enumerate() {
+            if PRIMITIVES.contains(&field.type_name) {
+                let value = (index % 100) as u8 + 1;
+                write_primitive(&mut bytes[field.offset.unwrap()..], field.type_name, value);
+                expected.push((field.name, if field.type_name == "bool" { Value::Bool(true) } else { Value::from(value) }));
+            }
+        }
+
+        let json = game_struct_to_json(version, class, &buffer, size)
+            .unwrap_or_else(|| panic!("ResourceLib doesn't accept {} bytes for {} in {:?}", size, class.name, version));
+        let json: Value = serde_json::from_str(&json).unwrap();
+        for (field, value) in expected {
+            let actual = &json[field];
+            let matches = match (actual.as_f64(), value.as_f64()) {
+                (Some(actual), Some(value)) => actual == value,
+                _ => *actual == value,
+            };
+            assert!(matches, "{}::{} in {:?}: expected {}, ResourceLib read {}", class.name, field, version, value, actual);
+        }
+
+        assert!(game_struct_to_json(version, class, &buffer, size + 1).is_none(), "ResourceLib accepts {} bytes for {} in {:?}", size + 1, class.name, version);
+    }
+}
+
+#[test]
+#[cfg(feature = "hm3")]
+fn test_hm3_layouts() {
+    let all_classes = [glacier_types::common::properties::CLASSES, glacier_hm3::properties::CLASSES].concat();
+    check_layouts(WoaVersion::HM3, glacier_types::common::properties::CLASSES, &all_classes);
+    check_layouts(WoaVersion::HM3, glacier_hm3::properties::CLASSES, &all_classes);
+}
+
+#[test]
+#[cfg(feature = "hm2")]
+fn test_hm2_layouts() {
+    let all_classes = [glacier_types::common::properties::CLASSES, glacier_hm2::properties::CLASSES].concat();
+    check_layouts(WoaVersion::HM2, glacier_hm2::properties::CLASSES, &all_classes);
+}
+
+#[test]
+#[cfg(feature = "hm2016")]
+fn test_hm2016_layouts() {
+    let all_classes = [glacier_types::common::properties::CLASSES, glacier_hm2016::properties::CLASSES].concat();
+    check_layouts(WoaVersion::HM2016, glacier_hm2016::properties::CLASSES, &all_classes);
+}
+
+#[test]
+#[cfg(feature = "hm3")]
+fn test_constants_match_the_type_model() {
+    use glacier_hm3::properties::SVector3;
+
+    let model = glacier_hm3::type_model();
+    for class in glacier_types::common::properties::CLASSES.iter().chain(glacier_hm3::properties::CLASSES) {
+        let declared = model.class(class.name).unwrap();
+        assert_eq!((class.size, class.alignment), (declared.size, declared.alignment), "{}", class.name);
+        for field in class.fields {
+            assert_eq!(field.offset, declared.field(field.name).unwrap().offset, "{}::{}", class.name, field.name);
+        }
+    }
+    assert_eq!((SVector3::SIZE, SVector3::ALIGN, SVector3::OFFSET_Z), (12, 4, 8));
+}
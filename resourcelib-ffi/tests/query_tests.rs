@@ -0,0 +1,105 @@
+#![cfg(all(feature = "temp", feature = "tblu", feature = "hm3"))]
+
+use resourcelib_ffi::quickentity::{Entity, Hm3EntityTemplate, QuickEntityConvert};
+use resourcelib_ffi::query::{property_id_from_name, EntityQuery, PropertyFilter};
+use serde_json::json;
+
+fn template() -> Hm3EntityTemplate {
+    let entity: Entity = serde_json::from_value(json!({
+        "tempHash": "00A1B2C3D4E5F607",
+        "tbluHash": "00A1B2C3D4E5F608",
+        "rootEntity": "0000000000000001",
+        "entities": {
+            "0000000000000001": {
+                "parent": null,
+                "name": "Root",
+                "factory": "[modules:/zentity.class].pc_entitytype",
+                "blueprint": "[modules:/zentity.class].pc_entityblueprint"
+            },
+            "0000000000000002": {
+                "parent": "0000000000000001",
+                "name": "Guard",
+                "factory": "[modules:/zactor.class].pc_entitytype",
+                "blueprint": "[modules:/zactor.class].pc_entityblueprint",
+                "properties": {
+                    "m_sActorName": { "type": "ZString", "value": "Guard" },
+                    "m_nHealth": { "type": "int32", "value": 100, "postInit": true }
+                }
+            },
+            "0000000000000003": {
+                "parent": "0000000000000001",
+                "name": "Civilian",
+                "factory": "[modules:/zactor.class].pc_entitytype",
+                "blueprint": "[modules:/zactor.class].pc_entityblueprint",
+                "properties": {
+                    "m_sActorName": { "type": "ZString", "value": "Civilian" }
+                }
+            }
+        },
+        "propertyOverrides": [],
+        "overrideDeletes": [],
+        "pinConnectionOverrides": [],
+        "pinConnectionOverrideDeletes": [],
+        "externalScenes": [],
+        "subType": "brick",
+        "quickEntityVersion": 3.1,
+        "extraFactoryDependencies": [],
+        "extraBlueprintDependencies": []
+    })).unwrap();
+    Hm3EntityTemplate::from_quick_entity(&entity).unwrap()
+}
+
+#[test]
+fn test_property_id_from_name() {
+    assert_eq!(property_id_from_name("123456789"), 0xCBF43926);
+}
+
+#[test]
+fn test_query_by_factory_and_property() {
+    let template = template();
+
+    let actors = EntityQuery::new()
+        .factory("[modules:/zactor.class].pc_entitytype")
+        .parent(0)
+        .entity_indices(&template);
+    assert_eq!(actors, vec![1, 2]);
+
+    let guards = EntityQuery::new()
+        .with_property(PropertyFilter::named("m_sActorName").of_type("ZString").equals(json!("Guard")))
+        .run(&template);
+    assert_eq!(guards.len(), 1);
+    assert_eq!(guards[0].entity_index, 1);
+    assert_eq!(guards[0].properties[0].to_string(), "subEntities[1].propertyValues[0]");
+
+    let post_init = EntityQuery::new()
+        .with_property(PropertyFilter::any().of_type("int32"))
+        .run(&template.factory);
+    assert_eq!(post_init[0].properties[0].to_string(), "subEntities[1].postInitPropertyValues[0]");
+
+    assert!(EntityQuery::new().name("Civilian").no_parent().run(&template).is_empty());
+}
+
+#[test]
+#[cfg(feature = "hm2016")]
+fn test_hm2016_templates() {
+    use glacier_hm2016::properties::{SEntityTemplateProperty, SEntityTemplateReference, STemplateEntity, STemplateSubEntity};
+    use glacier_types::{EntityTemplatePropertyId, ZString, ZVariant};
+
+    let sub_entity = STemplateSubEntity {
+        // A parent in an external scene isn't local.
+        logical_parent: SEntityTemplateReference { entity_index: -2, external_scene_index: 0, ..Default::default() },
+        property_values: vec![SEntityTemplateProperty {
+            n_property_id: EntityTemplatePropertyId::Str("m_sActorName".to_owned()),
+            value: <dyn ZVariant>::new(ZString::from("Guard")),
+        }],
+        ..Default::default()
+    };
+    let factory = STemplateEntity { entity_templates: vec![sub_entity], ..Default::default() };
+
+    let guards = EntityQuery::new()
+        .no_parent()
+        .with_property(PropertyFilter::named("m_sActorName"))
+        .run(&factory);
+    assert_eq!(guards.len(), 1);
+    assert_eq!(guards[0].properties[0].to_string(), "entityTemplates[0].propertyValues[0]");
+}
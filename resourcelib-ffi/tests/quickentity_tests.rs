@@ -0,0 +1,103 @@
+#![cfg(all(feature = "temp", feature = "tblu", feature = "hm3"))]
+
+use resourcelib_ffi::quickentity::{apply_patch, generate_patch, Entity, FullRef, Hm3EntityTemplate, QuickEntityConvert, Ref};
+use resourcelib_ffi::ResourceLibError;
+
+const ENTITY: &str = r##"{
+    "tempHash": "00A1B2C3D4E5F607",
+    "tbluHash": "00A1B2C3D4E5F608",
+    "rootEntity": "fffffffffffffffe",
+    "entities": {
+        "fffffffffffffffe": {
+            "parent": null,
+            "name": "Scene",
+            "factory": "[modules:/zentity.class].pc_entitytype",
+            "blueprint": "[modules:/zentity.class].pc_entityblueprint",
+            "properties": {
+                "m_bVisible": { "type": "bool", "value": true }
+            }
+        },
+        "feed00000000beef": {
+            "parent": "fffffffffffffffe",
+            "name": "Child",
+            "factory": "[modules:/zspatialentity.class].pc_entitytype",
+            "blueprint": "[modules:/zspatialentity.class].pc_entityblueprint",
+            "properties": {
+                "m_sName": { "type": "ZString", "value": "Child" },
+                "m_nCount": { "type": "int32", "value": 3, "postInit": true }
+            },
+            "events": {
+                "OnStart": { "Show": ["fffffffffffffffe"] }
+            }
+        }
+    },
+    "propertyOverrides": [],
+    "overrideDeletes": [],
+    "pinConnectionOverrides": [],
+    "pinConnectionOverrideDeletes": [],
+    "externalScenes": [],
+    "subType": "scene",
+    "quickEntityVersion": 3.1,
+    "extraFactoryDependencies": [],
+    "extraBlueprintDependencies": [],
+    "comments": []
+}"##;
+
+#[test]
+fn test_quick_entity_round_trip() {
+    let entity: Entity = serde_json::from_str(ENTITY).unwrap();
+    let template = Hm3EntityTemplate::from_quick_entity(&entity).unwrap();
+
+    assert_eq!(template.factory.sub_entities.len(), 2);
+    assert_eq!(template.blueprint.pin_connections.len(), 1);
+
+    let converted = template.to_quick_entity().unwrap();
+    let child = &converted.entities["feed00000000beef"];
+    assert_eq!(child.parent, entity.entities["feed00000000beef"].parent);
+    assert_eq!(child.events, entity.entities["feed00000000beef"].events);
+    assert_eq!(child.properties, entity.entities["feed00000000beef"].properties);
+    assert_eq!(template.factory.sub_entities[0].post_init_property_values.len(), 1);
+}
+
+#[test]
+fn test_duplicate_entity_ids() {
+    let entity: Entity = serde_json::from_str(ENTITY).unwrap();
+    let mut template = Hm3EntityTemplate::from_quick_entity(&entity).unwrap();
+    template.blueprint.sub_entities[1].entity_id = template.blueprint.sub_entities[0].entity_id;
+
+    assert!(matches!(template.to_quick_entity(), Err(ResourceLibError::InvalidEntityReference(_))));
+}
+
+#[test]
+fn test_external_scene_references() {
+    let mut entity: Entity = serde_json::from_str(ENTITY).unwrap();
+    entity.external_scenes.push("00B1B2C3D4E5F609".to_string());
+    entity.entities.get_mut("feed00000000beef").unwrap().parent = Ref::Full(FullRef {
+        entity_ref: "00000000000000aa".to_string(),
+        external_scene: Some("00B1B2C3D4E5F609".to_string()),
+        exposed_entity: None,
+    });
+    let mut template = Hm3EntityTemplate::from_quick_entity(&entity).unwrap();
+    assert_eq!(template.to_quick_entity().unwrap().entities["feed00000000beef"].parent, entity.entities["feed00000000beef"].parent);
+
+    template.factory.external_scene_type_indices_in_resource_header.clear();
+    assert!(matches!(template.to_quick_entity(), Err(ResourceLibError::InvalidResourceReference(0))));
+}
+
+#[test]
+fn test_quick_entity_patch() {
+    let original: Entity = serde_json::from_str(ENTITY).unwrap();
+    let mut modified = original.clone();
+    modified.entities.get_mut("feed00000000beef").unwrap().name = "Renamed".to_string();
+    assert!(modified.entities.remove("fffffffffffffffe").is_some());
+    modified.external_scenes.push("00B1B2C3D4E5F609".to_string());
+
+    let patch = generate_patch(&original, &modified);
+    assert_eq!(patch.patch.len(), 3);
+
+    let mut patched = original.clone();
+    apply_patch(&mut patched, &patch).unwrap();
+    assert_eq!(patched, modified);
+
+    assert!(apply_patch(&mut patched, &patch).is_err());
+}
@@ -1,4 +1,4 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "native"))]
 mod tests {
     use resourcelib_ffi::ResourceGenerator;
 use resourcelib_ffi::ResourceConverter;
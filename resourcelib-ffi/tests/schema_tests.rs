@@ -0,0 +1,99 @@
+#![cfg(all(feature = "hm3", feature = "temp", feature = "vidb", feature = "crmd"))]
+
+use glacier_hm3::properties::{SCrowdMapData, STemplateEntityFactory, SVector3, SVector4, SVideoDatabaseData, SVideoDatabaseEntry, ZRuntimeResourceId};
+use glacier_types::{TArray, ZString, ZVariant};
+use resourcelib_ffi::schema::{resource_types, schema_for};
+use resourcelib_ffi::WoaVersion;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+fn validator(schema: &Value) -> jsonschema::Validator {
+    jsonschema::validator_for(schema).unwrap()
+}
+
+fn is_valid<T: Serialize>(validator: &jsonschema::Validator, value: &T) -> bool {
+    validator.is_valid(&serde_json::to_value(value).unwrap())
+}
+
+#[test]
+fn test_schemas_accept_resources() {
+    let video_database = SVideoDatabaseData {
+        entries: vec![SVideoDatabaseEntry {
+            s_key: ZString::from("intro"),
+            rid: ZRuntimeResourceId { id_high: 0x00123456, id_low: 1 },
+            a_languages: vec![ZString::from("en")],
+            a_subtitle_languages: vec![],
+        }],
+    };
+    let vidb = validator(&schema_for(WoaVersion::HM3, "VIDB").unwrap());
+    assert!(is_valid(&vidb, &video_database));
+    assert!(!vidb.is_valid(&json!({ "entries": [{ "sKey": 5 }] })));
+    assert!(!vidb.is_valid(&json!({ "entries": [], "unknown": 0 })));
+
+    let crowd_map = SCrowdMapData {
+        v_min: SVector4 { x: -2.0, y: -1.0, z: 0.0, w: 1.0 },
+        v_max: SVector4 { x: 2.0, y: 1.0, z: 1.0, w: 1.0 },
+        n_cell_count_x: 1,
+        n_cell_count_y: 1,
+        f_cell_size: 1.0,
+        a_cell_flags: vec![255],
+    };
+    let crmd = validator(&schema_for(WoaVersion::HM3, "crmd").unwrap());
+    assert!(is_valid(&crmd, &crowd_map));
+    let mut json = serde_json::to_value(&crowd_map).unwrap();
+    json["m_aCellFlags"][0] = json!(256);
+    assert!(!crmd.is_valid(&json));
+
+    assert!(is_valid(&validator(&schema_for(WoaVersion::HM3, "TEMP").unwrap()), &STemplateEntityFactory::default()));
+}
+
+#[test]
+fn test_schemas_only_contain_needed_definitions() {
+    let schema = schema_for(WoaVersion::HM3, "VIDB").unwrap();
+    assert_eq!(schema["$ref"], "#/$defs/SVideoDatabaseData");
+    assert_eq!(schema["title"], "HM3 VIDB");
+    let definitions = schema["$defs"].as_object().unwrap();
+    assert!(definitions.contains_key("SVideoDatabaseEntry"));
+    assert!(definitions.contains_key("ZRuntimeResourceID"));
+    assert!(!definitions.contains_key("SCrowdMapData"));
+    assert!(!definitions.contains_key("ZVariant"));
+
+    assert!(resource_types(WoaVersion::HM3).contains(&"TEMP"));
+    assert_eq!(schema_for(WoaVersion::HM3, "NONE"), None);
+}
+
+#[test]
+fn test_variants() {
+    let mut schema = schema_for(WoaVersion::HM3, "TEMP").unwrap();
+    schema["$ref"] = json!("#/$defs/ZVariant");
+    let variant = validator(&schema);
+
+    let vector = SVector3 { x: 1.0, y: 2.0, z: 3.0 };
+    assert!(is_valid(&variant, &<dyn ZVariant>::new(vector.clone())));
+    assert!(is_valid(&variant, &<dyn ZVariant>::new(TArray::from(vec![vector]))));
+    assert!(is_valid(&variant, &<dyn ZVariant>::new(ZString::from("Hitman"))));
+    assert!(is_valid(&variant, &<Box<dyn ZVariant>>::default()));
+
+    assert!(!variant.is_valid(&json!({ "$type": "SVector3", "$val": { "x": "1" } })));
+    assert!(!variant.is_valid(&json!({ "$type": "TArray<SVector3>", "$val": [1.0] })));
+    assert!(!variant.is_valid(&json!({ "$type": "uint8", "$val": -1 })));
+    assert!(!variant.is_valid(&json!({ "$val": 1 })));
+    // The values of types the bindings don't know aren't checked.
+    assert!(variant.is_valid(&json!({ "$type": "SUnknown", "$val": { "anything": true } })));
+}
+
+#[test]
+#[cfg(all(feature = "all-types", feature = "unknown-enum-values"))]
+fn test_unknown_enum_values() {
+    use glacier_hm3::enums::EActorType;
+    use glacier_types::UnknownEnumValue;
+
+    let mut schema = schema_for(WoaVersion::HM3, "TEMP").unwrap();
+    schema["$ref"] = json!("#/$defs/EActorType");
+    let actor_type = validator(&schema);
+
+    assert!(is_valid(&actor_type, &EActorType::Guard));
+    assert!(is_valid(&actor_type, &EActorType::Unknown(UnknownEnumValue::Value(42))));
+    assert!(is_valid(&actor_type, &EActorType::Unknown(UnknownEnumValue::Name("eAT_FromANewerPatch".to_owned()))));
+    assert!(!actor_type.is_valid(&json!(1.5)));
+}
@@ -0,0 +1,41 @@
+#![cfg(all(feature = "all-types", feature = "hm2", feature = "hm3"))]
+
+use glacier_types::{TArray, ZVariant};
+use serde_json::json;
+
+fn length(vector: &glacier_hm3::properties::SVector3) -> f32 {
+    (vector.x * vector.x + vector.y * vector.y + vector.z * vector.z).sqrt()
+}
+
+#[test]
+fn test_shared_types_are_interchangeable() {
+    let vector = glacier_hm2::properties::SVector3 { x: 3.0, y: 4.0, z: 0.0 };
+    assert_eq!(length(&vector), 5.0);
+    assert!(glacier_types::common::type_model().class("SVector3").is_some());
+}
+
+#[test]
+fn test_shared_array_variant_round_trip() {
+    let json = json!({ "$type": "TArray<SVector3>", "$val": [{ "x": 1.0, "y": 2.0, "z": 3.0 }] });
+    let variant: Box<dyn ZVariant> = serde_json::from_value(json.clone()).unwrap();
+    assert!(variant.as_any().downcast_ref::<TArray<glacier_hm3::properties::SVector3>>().is_some());
+    assert_eq!(serde_json::to_value(&variant).unwrap(), json);
+}
+
+#[test]
+fn test_clone_and_compare_variants() {
+    use glacier_hm3::properties::{SEntityTemplateProperty, SVector3};
+    use glacier_types::EntityTemplatePropertyId;
+
+    let property = SEntityTemplateProperty {
+        n_property_id: EntityTemplatePropertyId::Str("m_vPosition".to_owned()),
+        value: Box::new(SVector3 { x: 1.0, y: 2.0, z: 3.0 }),
+    };
+    let mut copy = property.clone();
+    assert_eq!(copy, property);
+
+    copy.value = Box::new(1.0f32);
+    assert_ne!(copy, property);
+    assert_eq!(SVector3::default(), SVector3 { x: 0.0, y: 0.0, z: 0.0 });
+    assert_eq!(SEntityTemplateProperty::default().value, Box::new(glacier_types::Empty) as Box<dyn ZVariant>);
+}
@@ -0,0 +1,18 @@
+#![cfg(all(feature = "tblu", feature = "hm3"))]
+
+use glacier_hm3::properties::STemplateBlueprintSubEntity;
+use glacier_types::ZString;
+use serde_json::json;
+
+#[test]
+fn test_pairs_round_trip() {
+    let mut json = serde_json::to_value(STemplateBlueprintSubEntity::default()).unwrap();
+    json["exposedInterfaces"] = json!([["ZSpatialEntity", 3]]);
+    json["entitySubsets"] = json!([["AudioEmitters", { "entities": [1, 2] }]]);
+
+    let sub_entity: STemplateBlueprintSubEntity = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(sub_entity.exposed_interfaces, vec![(ZString::from("ZSpatialEntity"), 3)]);
+    assert_eq!(sub_entity.entity_subsets[0].0, ZString::from("AudioEmitters"));
+    assert_eq!(sub_entity.entity_subsets[0].1.entities, vec![1, 2]);
+    assert_eq!(serde_json::to_value(&sub_entity).unwrap(), json);
+}
@@ -0,0 +1,104 @@
+#![cfg(all(feature = "glam", feature = "temp", feature = "tblu", feature = "hm3"))]
+
+use glacier_hm3::properties::{SMatrix43, SQuaternion, SVector3};
+use glacier_types::glam::{Affine3A, Quat, Vec3};
+use resourcelib_ffi::quickentity::{Entity, Hm3EntityTemplate, QuickEntityConvert};
+use resourcelib_ffi::transform::{world_transform, world_transforms};
+use serde_json::json;
+
+fn template() -> Hm3EntityTemplate {
+    let entity: Entity = serde_json::from_value(json!({
+        "tempHash": "00A1B2C3D4E5F607",
+        "tbluHash": "00A1B2C3D4E5F608",
+        "rootEntity": "0000000000000001",
+        "entities": {
+            "0000000000000001": {
+                "parent": null,
+                "name": "Root",
+                "factory": "[modules:/zspatialentity.class].pc_entitytype",
+                "blueprint": "[modules:/zspatialentity.class].pc_entityblueprint",
+                "properties": {
+                    "m_mTransform": {
+                        "type": "SMatrix43",
+                        "value": { "rotation": { "x": 0, "y": 0, "z": 90 }, "position": { "x": 10, "y": 0, "z": 0 } }
+                    }
+                }
+            },
+            "0000000000000002": {
+                "parent": "0000000000000001",
+                "name": "Child",
+                "factory": "[modules:/zspatialentity.class].pc_entitytype",
+                "blueprint": "[modules:/zspatialentity.class].pc_entityblueprint",
+                "properties": {
+                    "m_mTransform": {
+                        "type": "SMatrix43",
+                        "value": { "rotation": { "x": 0, "y": 0, "z": 0 }, "position": { "x": 1, "y": 0, "z": 0 } }
+                    },
+                    "m_eidParent": { "type": "SEntityTemplateReference", "value": "0000000000000001" }
+                }
+            },
+            "0000000000000003": {
+                "parent": "0000000000000002",
+                "name": "Without spatial parent",
+                "factory": "[modules:/zentity.class].pc_entitytype",
+                "blueprint": "[modules:/zentity.class].pc_entityblueprint"
+            },
+            "0000000000000004": {
+                "parent": "0000000000000001",
+                "name": "Spatial child of Child",
+                "factory": "[modules:/zspatialentity.class].pc_entitytype",
+                "blueprint": "[modules:/zspatialentity.class].pc_entityblueprint",
+                "properties": {
+                    "m_mTransform": {
+                        "type": "SMatrix43",
+                        "value": { "rotation": { "x": 0, "y": 0, "z": 0 }, "position": { "x": 0, "y": 0, "z": 1 } }
+                    },
+                    "m_eidParent": { "type": "SEntityTemplateReference", "value": "0000000000000002" }
+                }
+            }
+        },
+        "propertyOverrides": [],
+        "overrideDeletes": [],
+        "pinConnectionOverrides": [],
+        "pinConnectionOverrideDeletes": [],
+        "externalScenes": [],
+        "subType": "brick",
+        "quickEntityVersion": 3.1,
+        "extraFactoryDependencies": [],
+        "extraBlueprintDependencies": []
+    })).unwrap();
+    Hm3EntityTemplate::from_quick_entity(&entity).unwrap()
+}
+
+#[test]
+fn test_vector_and_quaternion_conversions() {
+    let vector = SVector3 { x: 1.0, y: 2.0, z: 3.0 };
+    assert_eq!(Vec3::from(vector.clone()), Vec3::new(1.0, 2.0, 3.0));
+    assert_eq!(SVector3::from(Vec3::from(vector.clone())), vector);
+
+    let quaternion = SQuaternion { x: 0.0, y: 0.0, z: 0.0, w: 1.0 };
+    assert_eq!(Quat::from(quaternion), Quat::IDENTITY);
+}
+
+#[test]
+fn test_matrix_conversions() {
+    let transform = Affine3A::from_scale_rotation_translation(Vec3::splat(2.0), Quat::from_rotation_y(1.0), Vec3::new(4.0, 5.0, 6.0));
+    let matrix = SMatrix43::from(transform);
+    assert_eq!(matrix.trans, SVector3 { x: 4.0, y: 5.0, z: 6.0 });
+    assert_eq!(Affine3A::from(matrix), transform);
+}
+
+#[test]
+fn test_world_transforms_compose_along_spatial_parents() {
+    let template = template();
+    let transforms = world_transforms(&template.factory);
+    assert_eq!(transforms.len(), 4);
+
+    // Logical parents are ignored, only `m_eidParent` places an entity.
+    let positions = transforms.iter().map(|transform| transform.translation).collect::<Vec<_>>();
+    let expected = [Vec3::new(10.0, 0.0, 0.0), Vec3::new(10.0, 1.0, 0.0), Vec3::ZERO, Vec3::new(10.0, 1.0, 1.0)];
+    for (position, expected) in positions.iter().zip(expected) {
+        assert!(Vec3::from(*position).abs_diff_eq(expected, 1e-4), "{position} != {expected}");
+    }
+    assert_eq!(world_transform(&template.factory, 2), Some(transforms[2]));
+}
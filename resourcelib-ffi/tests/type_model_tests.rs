@@ -0,0 +1,19 @@
+#![cfg(all(feature = "all-types", feature = "hm2016", feature = "hm2", feature = "hm3"))]
+
+use glacier_types::ir::TypeRef;
+use resourcelib_ffi::codegen::type_model;
+use resourcelib_ffi::WoaVersion;
+
+#[test]
+fn test_type_model() {
+    for version in [WoaVersion::HM2016, WoaVersion::HM2, WoaVersion::HM3] {
+        let model = type_model(version).unwrap();
+        let entry = model.class("SVideoDatabaseEntry").expect("missing SVideoDatabaseEntry");
+        let languages = entry.field("aLanguages").expect("missing aLanguages");
+        assert_eq!(languages.type_ref.to_string(), "TArray<ZString>");
+        assert_eq!(languages.type_ref.args(), &[TypeRef::parse("ZString").unwrap()]);
+        assert!(model.enum_decl("EActorType").is_some_and(|actor_type| !actor_type.variants.is_empty()));
+    }
+    assert!(type_model(WoaVersion::HM3).unwrap().class("STemplateEntityFactory").is_some());
+    assert!(type_model(WoaVersion::HM2016).unwrap().class("STemplateEntityFactory").is_none());
+}
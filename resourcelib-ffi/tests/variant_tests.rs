@@ -0,0 +1,92 @@
+#![cfg(all(feature = "all-types", feature = "hm3"))]
+
+use glacier_hm3::properties::SVector3;
+use glacier_hm3::visitor::{visit, Visitor};
+use glacier_types::{TArray, ZString, ZVariant};
+use serde_json::json;
+
+#[test]
+fn test_accessors() {
+    let value = <dyn ZVariant>::new(5i32);
+    assert_eq!(value.type_name(), "int32");
+    assert_eq!(value.as_i32(), Some(5));
+    assert_eq!(value.as_u32(), None);
+    assert_eq!(value.as_str(), None);
+
+    let value = <dyn ZVariant>::new(ZString::from("Hitman"));
+    assert_eq!(value.type_name(), "ZString");
+    assert_eq!(value.as_str(), Some("Hitman"));
+
+    let value: Box<dyn ZVariant> = serde_json::from_value(json!({ "$type": "TArray<SVector3>", "$val": [{ "x": 1.0, "y": 2.0, "z": 3.0 }] })).unwrap();
+    assert_eq!(value.type_name(), "TArray<SVector3>");
+    assert_eq!(value.as_array::<SVector3>(), Some(&[SVector3 { x: 1.0, y: 2.0, z: 3.0 }][..]));
+    assert_eq!(value.as_array::<i32>(), None);
+    assert!(<dyn ZVariant>::new(glacier_types::Empty).is_void());
+}
+
+#[derive(Default)]
+struct Collector {
+    vectors: Vec<SVector3>,
+    arrays: usize,
+    others: Vec<&'static str>,
+}
+
+impl Visitor for Collector {
+    fn visit_other(&mut self, value: &dyn ZVariant) {
+        self.others.push(value.type_name());
+    }
+
+    fn visit_s_vector3(&mut self, value: &SVector3) {
+        self.vectors.push(value.clone());
+    }
+
+    fn visit_s_vector3_array(&mut self, value: &TArray<SVector3>) {
+        self.arrays += 1;
+        self.vectors.extend(value.as_slice().iter().cloned());
+    }
+}
+
+#[test]
+fn test_visitor() {
+    let vector = SVector3 { x: 1.0, y: 2.0, z: 3.0 };
+    let mut collector = Collector::default();
+    visit(&mut collector, <dyn ZVariant>::new(vector.clone()).as_ref());
+    visit(&mut collector, <dyn ZVariant>::new(TArray::from(vec![vector.clone()])).as_ref());
+    visit(&mut collector, <dyn ZVariant>::new(true).as_ref());
+
+    assert_eq!(collector.vectors, vec![vector.clone(), vector]);
+    assert_eq!(collector.arrays, 1);
+    assert_eq!(collector.others, vec!["bool"]);
+}
+
+#[test]
+fn test_reflection() {
+    use glacier_hm3::properties::SEntityTemplateProperty;
+    use glacier_types::reflect::{Reflect, ReflectError, TypeKind};
+
+    let mut property = SEntityTemplateProperty::default();
+    let type_info = property.type_info();
+    assert_eq!(type_info.name, "SEntityTemplateProperty");
+    assert_eq!(type_info.kind, TypeKind::Class);
+    assert_eq!(type_info.fields.iter().map(|field| field.name).collect::<Vec<_>>(), ["nPropertyID", "value"]);
+    assert_eq!(type_info.field("value").map(|field| field.type_name), Some("ZVariant"));
+
+    let value = json!({ "$type": "SVector3", "$val": { "x": 1.0, "y": 2.0, "z": 3.0 } });
+    property.set_field("value", value.clone()).unwrap();
+    assert_eq!(property.get_field("value").unwrap(), value);
+    assert_eq!(property.value.get::<SVector3>(), Some(&SVector3 { x: 1.0, y: 2.0, z: 3.0 }));
+
+    property.set_field("nPropertyID", json!("m_vPosition")).unwrap();
+    assert_eq!(property.get_field("nPropertyID").unwrap(), json!("m_vPosition"));
+
+    let vector = property.field_mut("value").and_then(|value| value.downcast_mut::<Box<dyn ZVariant>>()).unwrap();
+    *vector = <dyn ZVariant>::new(1.0f32);
+    assert_eq!(property.value.as_f32(), Some(1.0));
+
+    assert!(matches!(property.get_field("m_nUnknown"), Err(ReflectError::UnknownField { .. })));
+    assert!(matches!(property.set_field("value", json!(5)), Err(ReflectError::InvalidValue { field: "value", .. })));
+
+    let actor_type = glacier_hm3::enums::EActorType::default();
+    assert_eq!(actor_type.type_info().kind, TypeKind::Enum);
+    assert!(actor_type.type_info().variants.contains(&"eAT_Civilian"));
+}
@@ -0,0 +1,71 @@
+#![cfg(all(feature = "vidb", feature = "hm3"))]
+
+use glacier_hm3::properties::SVideoDatabaseData;
+use resourcelib_ffi::vidb::{VideoDatabase, VideoDatabaseData, VideoEntry};
+use resourcelib_ffi::ResourceLibError;
+
+fn entry(key: &str, id_low: u32) -> VideoEntry {
+    VideoEntry {
+        key: key.to_string(),
+        resource_id: (0, id_low),
+        languages: vec!["en".to_string(), "fr".to_string()],
+        subtitle_languages: vec!["en".to_string(), "de".to_string()],
+    }
+}
+
+#[test]
+fn test_edit_entries() {
+    let mut database = VideoDatabase::new(vec![entry("intro", 0), entry("outro", 1)]).unwrap();
+    assert_eq!(database.variants("intro"), Some(vec!["en", "fr", "de"]));
+
+    assert!(matches!(database.add(entry("intro", 2)), Err(ResourceLibError::DuplicateVideoKey(_))));
+    assert!(matches!(database.rename("outro", "intro"), Err(ResourceLibError::DuplicateVideoKey(_))));
+    assert!(matches!(database.add(entry("", 2)), Err(ResourceLibError::EmptyVideoKey)));
+    assert!(matches!(database.rename("outro", ""), Err(ResourceLibError::EmptyVideoKey)));
+    assert!(matches!(database.remove("credits"), Err(ResourceLibError::UnknownVideoKey(_))));
+
+    database.rename("outro", "credits").unwrap();
+    assert_eq!(database.get("credits").map(|entry| entry.resource_id), Some((0, 1)));
+    database.set_resource_id("credits", (0, 3)).unwrap();
+    database.set_languages("credits", vec!["it".to_string()]).unwrap();
+    database.set_subtitle_languages("credits", vec![]).unwrap();
+    assert_eq!(database.variants("credits"), Some(vec!["it"]));
+    assert!(matches!(database.set_resource_id("outro", (0, 4)), Err(ResourceLibError::UnknownVideoKey(_))));
+    assert_eq!(database.remove("intro").unwrap().key, "intro");
+    assert_eq!(database.len(), 1);
+}
+
+#[test]
+fn test_csv_round_trip() {
+    let database = VideoDatabase::new(vec![entry("intro, part \"one\"", 0), entry("outro", 1)]).unwrap();
+    let mut csv = Vec::new();
+    database.write_csv(&mut csv).unwrap();
+    assert_eq!(VideoDatabase::read_csv(csv.as_slice()).unwrap(), database);
+
+    let duplicate = b"key,resource_id_high,resource_id_low,languages,subtitle_languages\na,0,0,en,\na,0,1,en,\n";
+    assert!(matches!(VideoDatabase::read_csv(&duplicate[..]), Err(ResourceLibError::DuplicateVideoKey(_))));
+}
+
+#[test]
+fn test_csv_errors() {
+    let read = |csv: &str| VideoDatabase::read_csv(csv.as_bytes()).unwrap_err();
+    let header = "key,resource_id_high,resource_id_low,languages,subtitle_languages\n";
+    let error = read("key,languages\n");
+    assert!(matches!(error, ResourceLibError::InvalidCsv(1, "header")), "{}", error);
+    let error = read(&format!("{}intro,0,0,en,\noutro,0,x,en,\n", header));
+    assert!(matches!(error, ResourceLibError::InvalidCsv(3, "resource_id_low")), "{}", error);
+    let error = read(&format!("{}intro,0,0,en\n", header));
+    assert!(matches!(error, ResourceLibError::CsvFieldCount(2, 5)), "{}", error);
+    let error = read(&format!("{}\"intro,0,0,en,\n", header));
+    assert!(matches!(error, ResourceLibError::InvalidCsv(2, "key")), "{}", error);
+}
+
+#[test]
+fn test_resource_round_trip() {
+    let database = VideoDatabase::new(vec![entry("intro", 0), entry("outro", 1)]).unwrap();
+    let data = SVideoDatabaseData::from_video_database(&database);
+
+    let json = serde_json::to_string(&data).unwrap();
+    let parsed: SVideoDatabaseData = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.to_video_database().unwrap(), database);
+}